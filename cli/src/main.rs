@@ -1,8 +1,36 @@
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use regex_data_gen_core::adversarial;
+use regex_data_gen_core::data_generator::ANY_PATTERN;
+use regex_data_gen_core::datetime::parse_offset;
 use regex_data_gen_core::{
-    CsvExporter, DataGenerator, Exporter, GenerationMode, JsonExporter, TsvExporter, XmlExporter,
+    Alignment, CharFilter, Checksum, CsvExporter, CsvImporter, DataGenerator, DatabaseGenerator,
+    DatabaseSchema, DateTimeSpec, DictionarySource, DocumentLayout, Exporter, FixedWidthExporter,
+    GenerationMode, HtmlExporter, Importer, JsonExporter, JsonImporter, JsonLinesExporter,
+    MarkdownExporter, NamedPattern, NumericRange, RecordGenerator, RecordSet, RegexEngine,
+    SchemaDocument, SqlDialect, SqlExporter, TomlExporter, Transform, TsvExporter, TsvImporter,
+    XmlExporter, XmlImporter, YamlExporter,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "bson")]
+use regex_data_gen_core::BsonExporter;
+#[cfg(feature = "cbor")]
+use regex_data_gen_core::CborExporter;
+#[cfg(feature = "msgpack")]
+use regex_data_gen_core::MessagePackExporter;
+#[cfg(feature = "sqlite")]
+use regex_data_gen_core::SqliteExporter;
+#[cfg(feature = "arrow")]
+use regex_data_gen_core::{ArrowIpcExporter, ArrowIpcFormat};
+#[cfg(feature = "avro")]
+use regex_data_gen_core::{AvroCompression, AvroExporter};
+#[cfg(feature = "parquet")]
+use regex_data_gen_core::{ParquetCompression, ParquetExporter};
+
+const DEFAULT_COUNT: usize = 10;
+
+/// Values generated at a time when streaming output
+const STREAM_BATCH_SIZE: usize = 1_000;
 
 #[derive(Parser)]
 #[command(name = "regex-data-gen")]
@@ -15,25 +43,34 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    Generate(GenerateArgs),
+    Generate(Box<GenerateArgs>),
     Validate(ValidateArgs),
+    Infer(InferArgs),
+    Diff(DiffArgs),
+    Check(CheckArgs),
+    /// List the built-in named patterns usable as `@name` or `@{name}`
+    Patterns,
 }
 
 #[derive(Args)]
 struct GenerateArgs {
-    /// Regex pattern to generate data from
-    #[arg(short, long)]
-    pattern: String,
+    /// Regex pattern to generate data from, or a named pattern such as @email
+    #[arg(short, long, required_unless_present_any = ["schema", "datetime"], conflicts_with = "schema")]
+    pattern: Option<String>,
 
-    /// Number of data items to generate
-    #[arg(short = 'n', long, default_value = "10")]
-    count: usize,
+    /// Schema file (TOML, YAML or JSON) describing multi-column records
+    #[arg(long)]
+    schema: Option<PathBuf>,
+
+    /// Number of data items to generate [default: 10, or the schema's count]
+    #[arg(short = 'n', long)]
+    count: Option<usize>,
 
     /// Output format
     #[arg(short, long, default_value = "csv")]
     format: OutputFormat,
 
-    /// Output file path
+    /// Output file path, or output directory for schemas with several tables
     #[arg(short, long)]
     output: PathBuf,
 
@@ -44,6 +81,106 @@ struct GenerateArgs {
     /// Generation mode
     #[arg(short, long, default_value = "random")]
     mode: GenerationModeArg,
+
+    /// Give every value a valid check digit
+    #[arg(long, conflicts_with = "schema")]
+    checksum: Option<ChecksumArg>,
+
+    /// Generate real dates and times: date, time, iso8601, rfc3339, rfc2822 or a strftime format
+    #[arg(long, conflicts_with = "schema")]
+    datetime: Option<String>,
+
+    /// Earliest date or time, as 2024-01-01 or an RFC 3339 timestamp
+    #[arg(long, requires = "datetime")]
+    start: Option<String>,
+
+    /// Latest date or time; a plain date includes the whole day
+    #[arg(long, requires = "datetime")]
+    end: Option<String>,
+
+    /// UTC offset of generated times, as +02:00 or Z
+    #[arg(long, requires = "datetime", allow_hyphen_values = true)]
+    utc_offset: Option<String>,
+
+    /// Limit a named capture group to a numeric range, as octet=0..255 or price=0.00..9.99,step=0.05,pad=5
+    #[arg(long = "range", value_parser = parse_group_range, conflicts_with = "schema")]
+    ranges: Vec<(String, NumericRange)>,
+
+    /// Word list for {{dict:NAME}} placeholders, as NAME=words.txt or NAME=data.csv,column=city[,weight_column=pop][,unique]
+    #[arg(long = "dict", value_parser = parse_dictionary, conflicts_with = "schema")]
+    dictionaries: Vec<(String, DictionarySource)>,
+
+    /// Rewrite every value; repeat to apply several transforms in order
+    #[arg(long = "transform", conflicts_with = "schema")]
+    transforms: Vec<TransformArg>,
+
+    /// Only generate characters of a Unicode script from classes, such as Latin or Han; repeatable
+    #[arg(long = "script", conflicts_with = "schema")]
+    scripts: Vec<String>,
+
+    /// Only generate characters of a Unicode general category from classes, such as Lu or Nd; repeatable
+    #[arg(long = "category", conflicts_with = "schema")]
+    categories: Vec<String>,
+
+    /// Characters classes may generate, in addition to any scripts and categories
+    #[arg(long, conflicts_with = "schema")]
+    allow_chars: Option<String>,
+
+    /// Characters classes must never generate
+    #[arg(long, conflicts_with = "schema")]
+    deny_chars: Option<String>,
+
+    /// Deliberately generate difficult Unicode characters, exporting why each value is tricky
+    #[arg(long, conflicts_with = "schema")]
+    adversarial: bool,
+
+    /// Rows per Parquet row group or Arrow record batch
+    #[arg(long, default_value_t = 1_048_576)]
+    row_group_size: usize,
+
+    /// Compression codec: snappy or zstd for Parquet [default: snappy], snappy or deflate for Avro [default: none]
+    #[arg(long)]
+    compression: Option<CompressionArg>,
+
+    /// Write Arrow columns as LargeUtf8, with 64-bit offsets, instead of Utf8
+    #[arg(long)]
+    large_utf8: bool,
+
+    /// YAML or TOML document shape
+    #[arg(long, default_value = "list")]
+    layout: LayoutArg,
+
+    /// Width of every fixed-width column [default: the pattern's maximum length]
+    #[arg(long)]
+    width: Option<usize>,
+
+    /// Side of fixed-width fields that values are aligned to
+    #[arg(long, default_value = "left")]
+    align: AlignArg,
+
+    /// Character padding fixed-width fields
+    #[arg(long, default_value_t = ' ')]
+    pad_char: char,
+
+    /// Truncate values longer than their fixed-width column instead of failing
+    #[arg(long)]
+    truncate: bool,
+
+    /// SQL dialect used to quote identifiers and strings
+    #[arg(long, default_value = "postgres")]
+    dialect: DialectArg,
+
+    /// SQL or SQLite table name [default: the output file name]
+    #[arg(long)]
+    table: Option<String>,
+
+    /// Rows per SQL INSERT statement
+    #[arg(long, default_value_t = 100)]
+    batch_size: usize,
+
+    /// Start SQL output with a CREATE TABLE statement, sized from the pattern's maximum length
+    #[arg(long)]
+    create_table: bool,
 }
 
 #[derive(Args)]
@@ -52,12 +189,149 @@ struct ValidateArgs {
     pattern: String,
 }
 
+#[derive(Args)]
+struct InferArgs {
+    /// Example strings to infer a pattern from
+    samples: Vec<String>,
+
+    /// File with one example string per line
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct DiffArgs {
+    /// Original regex pattern
+    old: String,
+
+    /// Updated regex pattern
+    new: String,
+
+    /// Maximum number of example strings to show per side
+    #[arg(short, long, default_value = "5")]
+    limit: usize,
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    /// Regex pattern every value must match
+    #[arg(short, long)]
+    pattern: String,
+
+    /// Dataset file to check
+    #[arg(short, long)]
+    input: PathBuf,
+
+    /// Column, field or element holding the values (defaults to the exporter's name)
+    #[arg(short, long)]
+    column: Option<String>,
+
+    /// Input format (inferred from the file extension when omitted)
+    #[arg(short, long)]
+    format: Option<OutputFormat>,
+
+    /// Number of failing rows to show
+    #[arg(long, default_value = "10")]
+    show: usize,
+
+    /// Require a named capture group to hold a number in range, as age=18..99
+    #[arg(long = "range", value_parser = parse_group_range)]
+    ranges: Vec<(String, NumericRange)>,
+}
+
 #[derive(Clone, ValueEnum)]
 enum OutputFormat {
     Csv,
     Json,
+    /// JSON Lines, one value per line, streamed as values are generated
+    Jsonl,
     Xml,
     Tsv,
+    /// Apache Parquet, streamed a row group at a time
+    #[cfg(feature = "parquet")]
+    Parquet,
+    /// Arrow IPC file, also known as Feather v2
+    #[cfg(feature = "arrow")]
+    Arrow,
+    /// Arrow IPC stream
+    #[cfg(feature = "arrow")]
+    #[value(name = "arrows")]
+    ArrowStream,
+    /// Avro object container file
+    #[cfg(feature = "avro")]
+    Avro,
+    /// MessagePack, laid out like JSON
+    #[cfg(feature = "msgpack")]
+    Msgpack,
+    /// CBOR, laid out like JSON
+    #[cfg(feature = "cbor")]
+    Cbor,
+    /// BSON documents, one per value or record
+    #[cfg(feature = "bson")]
+    Bson,
+    Yaml,
+    Toml,
+    /// Fixed-width records, one per line
+    Fixed,
+    /// Markdown pipe table
+    Markdown,
+    /// Standalone HTML page with a table
+    Html,
+    /// SQL INSERT statements
+    Sql,
+    /// SQLite database file, created or appended to
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+}
+
+impl OutputFormat {
+    fn from_extension(path: &std::path::Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "csv" => Some(OutputFormat::Csv),
+            "json" => Some(OutputFormat::Json),
+            "jsonl" | "ndjson" => Some(OutputFormat::Jsonl),
+            "xml" => Some(OutputFormat::Xml),
+            "tsv" | "tab" => Some(OutputFormat::Tsv),
+            #[cfg(feature = "parquet")]
+            "parquet" => Some(OutputFormat::Parquet),
+            #[cfg(feature = "arrow")]
+            "arrow" | "feather" | "ipc" => Some(OutputFormat::Arrow),
+            #[cfg(feature = "arrow")]
+            "arrows" => Some(OutputFormat::ArrowStream),
+            #[cfg(feature = "avro")]
+            "avro" => Some(OutputFormat::Avro),
+            #[cfg(feature = "msgpack")]
+            "msgpack" | "mpk" => Some(OutputFormat::Msgpack),
+            #[cfg(feature = "cbor")]
+            "cbor" => Some(OutputFormat::Cbor),
+            #[cfg(feature = "bson")]
+            "bson" => Some(OutputFormat::Bson),
+            "yaml" | "yml" => Some(OutputFormat::Yaml),
+            "toml" => Some(OutputFormat::Toml),
+            "fixed" | "fwf" => Some(OutputFormat::Fixed),
+            "md" | "markdown" => Some(OutputFormat::Markdown),
+            "html" | "htm" => Some(OutputFormat::Html),
+            "sql" => Some(OutputFormat::Sql),
+            #[cfg(feature = "sqlite")]
+            "sqlite" | "sqlite3" | "db" => Some(OutputFormat::Sqlite),
+            _ => None,
+        }
+    }
+
+    /// Whether random values can be written as they are generated
+    fn is_streamable(&self) -> bool {
+        match self {
+            OutputFormat::Jsonl => true,
+            #[cfg(feature = "parquet")]
+            OutputFormat::Parquet => true,
+            #[cfg(feature = "arrow")]
+            OutputFormat::Arrow | OutputFormat::ArrowStream => true,
+            #[cfg(feature = "avro")]
+            OutputFormat::Avro => true,
+            _ => false,
+        }
+    }
 }
 
 impl std::fmt::Display for OutputFormat {
@@ -65,13 +339,36 @@ impl std::fmt::Display for OutputFormat {
         match self {
             OutputFormat::Csv => write!(f, "csv"),
             OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Jsonl => write!(f, "jsonl"),
             OutputFormat::Xml => write!(f, "xml"),
             OutputFormat::Tsv => write!(f, "tsv"),
+            #[cfg(feature = "parquet")]
+            OutputFormat::Parquet => write!(f, "parquet"),
+            #[cfg(feature = "arrow")]
+            OutputFormat::Arrow => write!(f, "arrow"),
+            #[cfg(feature = "arrow")]
+            OutputFormat::ArrowStream => write!(f, "arrows"),
+            #[cfg(feature = "avro")]
+            OutputFormat::Avro => write!(f, "avro"),
+            #[cfg(feature = "msgpack")]
+            OutputFormat::Msgpack => write!(f, "msgpack"),
+            #[cfg(feature = "cbor")]
+            OutputFormat::Cbor => write!(f, "cbor"),
+            #[cfg(feature = "bson")]
+            OutputFormat::Bson => write!(f, "bson"),
+            OutputFormat::Yaml => write!(f, "yaml"),
+            OutputFormat::Toml => write!(f, "toml"),
+            OutputFormat::Fixed => write!(f, "fixed"),
+            OutputFormat::Markdown => write!(f, "markdown"),
+            OutputFormat::Html => write!(f, "html"),
+            OutputFormat::Sql => write!(f, "sql"),
+            #[cfg(feature = "sqlite")]
+            OutputFormat::Sqlite => write!(f, "sqlite"),
         }
     }
 }
 
-#[derive(Clone, ValueEnum)]
+#[derive(Clone, Copy, ValueEnum)]
 enum GenerationModeArg {
     Random,
     Sequential,
@@ -88,13 +385,138 @@ impl From<GenerationModeArg> for GenerationMode {
     }
 }
 
+#[derive(Clone, ValueEnum)]
+enum ChecksumArg {
+    Luhn,
+    Isbn10,
+    Isbn13,
+    Iban,
+    Ean8,
+    Ean13,
+}
+
+impl From<ChecksumArg> for Checksum {
+    fn from(checksum: ChecksumArg) -> Self {
+        match checksum {
+            ChecksumArg::Luhn => Checksum::Luhn,
+            ChecksumArg::Isbn10 => Checksum::Isbn10,
+            ChecksumArg::Isbn13 => Checksum::Isbn13,
+            ChecksumArg::Iban => Checksum::Iban,
+            ChecksumArg::Ean8 => Checksum::Ean8,
+            ChecksumArg::Ean13 => Checksum::Ean13,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CompressionArg {
+    None,
+    Snappy,
+    Deflate,
+    Zstd,
+}
+
+impl std::fmt::Display for CompressionArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompressionArg::None => write!(f, "none"),
+            CompressionArg::Snappy => write!(f, "snappy"),
+            CompressionArg::Deflate => write!(f, "deflate"),
+            CompressionArg::Zstd => write!(f, "zstd"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LayoutArg {
+    /// A list of values, or of records as maps
+    List,
+    /// A list of {id, value} maps
+    Objects,
+    /// A map keyed by value, or by the first column of records
+    Map,
+}
+
+impl From<LayoutArg> for DocumentLayout {
+    fn from(layout: LayoutArg) -> Self {
+        match layout {
+            LayoutArg::List => DocumentLayout::List,
+            LayoutArg::Objects => DocumentLayout::Objects,
+            LayoutArg::Map => DocumentLayout::Map,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum AlignArg {
+    Left,
+    Right,
+}
+
+impl From<AlignArg> for Alignment {
+    fn from(align: AlignArg) -> Self {
+        match align {
+            AlignArg::Left => Alignment::Left,
+            AlignArg::Right => Alignment::Right,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DialectArg {
+    Postgres,
+    Mysql,
+    Sqlite,
+}
+
+impl From<DialectArg> for SqlDialect {
+    fn from(dialect: DialectArg) -> Self {
+        match dialect {
+            DialectArg::Postgres => SqlDialect::Postgres,
+            DialectArg::Mysql => SqlDialect::MySql,
+            DialectArg::Sqlite => SqlDialect::Sqlite,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TransformArg {
+    Upper,
+    Lower,
+    Title,
+    RandomCase,
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+impl From<TransformArg> for Transform {
+    fn from(transform: TransformArg) -> Self {
+        match transform {
+            TransformArg::Upper => Transform::Upper,
+            TransformArg::Lower => Transform::Lower,
+            TransformArg::Title => Transform::Title,
+            TransformArg::RandomCase => Transform::RandomCase,
+            TransformArg::Nfc => Transform::Nfc,
+            TransformArg::Nfd => Transform::Nfd,
+            TransformArg::Nfkc => Transform::Nfkc,
+            TransformArg::Nfkd => Transform::Nfkd,
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Generate(args) => generate_data(args).await,
+        Commands::Generate(args) => generate_data(*args).await,
         Commands::Validate(args) => validate_pattern(args).await,
+        Commands::Infer(args) => infer_pattern(args).await,
+        Commands::Diff(args) => diff_patterns(args).await,
+        Commands::Check(args) => check_dataset(args).await,
+        Commands::Patterns => list_patterns().await,
     }
 }
 
@@ -106,35 +528,136 @@ async fn generate_data(args: GenerateArgs) -> anyhow::Result<()> {
             std::process::exit(1);
         }
 
-    println!(
-        "🔄 Generating {} items from pattern: '{}'",
-        args.count, args.pattern
-    );
+    if let Some(schema_path) = &args.schema {
+        return generate_records(&args, schema_path).await;
+    }
+
+    let count = args.count.unwrap_or(DEFAULT_COUNT);
 
-    let mut generator = if let Some(seed) = args.seed {
-        match DataGenerator::with_seed(&args.pattern, seed) {
-            Ok(generator) => {
-                println!("ℹ️  Using seed: {} for reproducible generation", seed);
-                generator
+    let mut builder = match (&args.pattern, &args.datetime) {
+        (Some(pattern), _) => {
+            println!("🔄 Generating {} items from pattern: '{}'", count, pattern);
+            DataGenerator::builder(pattern)
+        }
+        (None, Some(format)) => {
+            println!("🔄 Generating {} dates in format: '{}'", count, format);
+            DataGenerator::builder(ANY_PATTERN)
+        }
+        (None, None) => unreachable!("clap requires a pattern, schema or datetime"),
+    };
+    for (group, range) in &args.ranges {
+        builder = builder.range(group, range.clone());
+    }
+    for (name, source) in &args.dictionaries {
+        match source.load() {
+            Ok(dictionary) => {
+                println!(
+                    "📖 Loaded {} entries into dictionary '{}'",
+                    dictionary.entries().len(),
+                    name
+                );
+                builder = builder.dictionary(name, dictionary);
             }
             Err(e) => {
-                eprintln!("❌ Failed to create generator: {}", e);
+                eprintln!("❌ {}", e);
                 std::process::exit(1);
             }
         }
-    } else {
-        match DataGenerator::new(&args.pattern) {
-            Ok(generator) => generator,
+    }
+    if let Some(format) = &args.datetime {
+        match datetime_spec(format, &args) {
+            Ok(spec) => builder = builder.datetime(spec),
             Err(e) => {
-                eprintln!("❌ Failed to create generator: {}", e);
+                eprintln!("❌ {}", e);
                 std::process::exit(1);
             }
         }
+    }
+    if let Some(seed) = args.seed {
+        println!("ℹ️  Using seed: {} for reproducible generation", seed);
+        builder = builder.seed(seed);
+    }
+    if let Some(checksum) = args.checksum.clone() {
+        let checksum = Checksum::from(checksum);
+        println!("ℹ️  Applying {} check digits", checksum.name());
+        builder = builder.checksum(checksum);
+    }
+    match char_filter(&args) {
+        Ok(Some(filter)) => builder = builder.filter(filter),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    }
+    for transform in &args.transforms {
+        let transform = Transform::from(*transform);
+        println!("ℹ️  Applying the {} transform", transform.name());
+        builder = builder.transform(transform);
+    }
+
+    if args.adversarial {
+        builder = builder.adversarial(true);
+    }
+
+    let mut generator = match builder.build() {
+        Ok(generator) => generator,
+        Err(e) => {
+            eprintln!("❌ Failed to create generator: {}", e);
+            std::process::exit(1);
+        }
     };
 
+    if args.adversarial {
+        if generator.tricks().is_empty() {
+            println!("⚠️  Pattern allows no difficult characters, values are generated as usual");
+        } else {
+            let tricks: Vec<&str> = generator.tricks().iter().map(|t| t.description()).collect();
+            println!("😈 Generating difficult characters: {}", tricks.join(", "));
+        }
+    }
+
     let generation_mode: GenerationMode = args.mode.into();
 
-    let data = match generator.generate_with_mode(args.count, generation_mode) {
+    if args.format.is_streamable() && generation_mode == GenerationMode::Random && !args.adversarial
+    {
+        let output_path = args.output.to_string_lossy();
+        println!(
+            "💾 Streaming to {} format...",
+            args.format.to_string().to_uppercase()
+        );
+        let streamed = match args.format {
+            #[cfg(feature = "parquet")]
+            OutputFormat::Parquet => {
+                stream_parquet(&mut generator, count, &output_path, parquet_exporter(&args))
+            }
+            #[cfg(feature = "arrow")]
+            OutputFormat::Arrow | OutputFormat::ArrowStream => stream_arrow(
+                &mut generator,
+                count,
+                &output_path,
+                arrow_exporter(&args.format, &args),
+            ),
+            #[cfg(feature = "avro")]
+            OutputFormat::Avro => {
+                stream_avro(&mut generator, count, &output_path, avro_exporter(&args))
+            }
+            _ => stream_json_lines(&mut generator, count, &output_path),
+        };
+        match streamed {
+            Ok(()) => println!(
+                "🎉 Successfully exported {} items to '{}'",
+                count, output_path
+            ),
+            Err(e) => {
+                eprintln!("❌ Streaming failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    let data = match generator.generate_with_mode(count, generation_mode) {
         Ok(data) => {
             println!("✅ Successfully generated {} items", data.len());
             data
@@ -148,30 +671,278 @@ async fn generate_data(args: GenerateArgs) -> anyhow::Result<()> {
     let output_path = args.output.to_string_lossy();
     println!("💾 Exporting to {} format...", args.format.to_string().to_uppercase());
 
-    let export_result = match args.format {
-        OutputFormat::Csv => {
-            let exporter = CsvExporter::new();
-            exporter.export(&data, &output_path)
+    let column = if args.adversarial {
+        "value"
+    } else {
+        "generated_data"
+    };
+    let table = TableInfo {
+        name: table_name(&args),
+        max_lengths: generator
+            .max_length()
+            .map(|length| (column.to_string(), length))
+            .into_iter()
+            .collect(),
+        // Sequential modes never repeat a value
+        unique_columns: if generation_mode == GenerationMode::Random {
+            Vec::new()
+        } else {
+            vec![column.to_string()]
+        },
+    };
+    let exporter = create_exporter(&args.format, &args, &table);
+    let exported = if args.adversarial {
+        // Report why each value is tricky next to it
+        let mut records = RecordSet::new(vec!["value".to_string(), "reasons".to_string()]);
+        for value in &data {
+            let reasons: Vec<String> = adversarial::explain(value)
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            records.push_row(vec![value.clone(), reasons.join("; ")])?;
+        }
+        exporter.export_records(&records, &output_path)
+    } else {
+        exporter.export(&data, &output_path)
+    };
+    match exported {
+        Ok(()) => {
+            println!(
+                "🎉 Successfully exported {} items to '{}'",
+                data.len(),
+                output_path
+            );
+        }
+        Err(e) => {
+            eprintln!("❌ Export failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate `count` values in batches, so large counts are never held in memory
+fn generate_in_batches(
+    generator: &mut DataGenerator,
+    count: usize,
+    mut write: impl FnMut(&str) -> regex_data_gen_core::Result<()>,
+) -> regex_data_gen_core::Result<()> {
+    let mut written = 0;
+    while written < count {
+        let batch = generator.generate(STREAM_BATCH_SIZE.min(count - written))?;
+        for value in &batch {
+            write(value)?;
+        }
+        written += batch.len();
+    }
+    Ok(())
+}
+
+fn stream_json_lines(
+    generator: &mut DataGenerator,
+    count: usize,
+    output_path: &str,
+) -> regex_data_gen_core::Result<()> {
+    let file = std::fs::File::create(output_path)?;
+    let mut writer = JsonLinesExporter::new().writer(std::io::BufWriter::new(file));
+    generate_in_batches(generator, count, |value| writer.write(value))?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+fn stream_parquet(
+    generator: &mut DataGenerator,
+    count: usize,
+    output_path: &str,
+    exporter: ParquetExporter,
+) -> regex_data_gen_core::Result<()> {
+    let file = std::fs::File::create(output_path)?;
+    let mut writer = exporter.writer(std::io::BufWriter::new(file))?;
+    generate_in_batches(generator, count, |value| writer.write(value))?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(feature = "avro")]
+fn stream_avro(
+    generator: &mut DataGenerator,
+    count: usize,
+    output_path: &str,
+    exporter: AvroExporter,
+) -> regex_data_gen_core::Result<()> {
+    let file = std::fs::File::create(output_path)?;
+    let mut writer = exporter.writer(std::io::BufWriter::new(file))?;
+    generate_in_batches(generator, count, |value| writer.write(value))?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(feature = "arrow")]
+fn stream_arrow(
+    generator: &mut DataGenerator,
+    count: usize,
+    output_path: &str,
+    exporter: ArrowIpcExporter,
+) -> regex_data_gen_core::Result<()> {
+    let file = std::fs::File::create(output_path)?;
+    let mut writer = exporter.writer(std::io::BufWriter::new(file))?;
+    generate_in_batches(generator, count, |value| writer.write(value))?;
+    writer.finish()?;
+    Ok(())
+}
+
+fn parse_group_range(spec: &str) -> Result<(String, NumericRange), String> {
+    let (group, range) = spec
+        .split_once('=')
+        .ok_or_else(|| format!("expected GROUP=MIN..MAX, got '{}'", spec))?;
+    let range = range
+        .parse()
+        .map_err(|e: regex_data_gen_core::Error| e.to_string())?;
+    Ok((group.to_string(), range))
+}
+
+fn parse_dictionary(spec: &str) -> Result<(String, DictionarySource), String> {
+    let mut parts = spec.split(',');
+    let (name, path) = parts
+        .next()
+        .and_then(|first| first.split_once('='))
+        .ok_or_else(|| format!("expected NAME=PATH, got '{}'", spec))?;
+
+    let mut source = DictionarySource {
+        path: PathBuf::from(path),
+        column: None,
+        weight_column: None,
+        unique: false,
+    };
+    for option in parts {
+        match option.split_once('=') {
+            Some(("column", column)) => source.column = Some(column.to_string()),
+            Some(("weight_column", column)) => source.weight_column = Some(column.to_string()),
+            None if option == "unique" => source.unique = true,
+            _ => return Err(format!("unknown dictionary option '{}'", option)),
+        }
+    }
+    Ok((name.to_string(), source))
+}
+
+fn char_filter(args: &GenerateArgs) -> regex_data_gen_core::Result<Option<CharFilter>> {
+    if args.scripts.is_empty()
+        && args.categories.is_empty()
+        && args.allow_chars.is_none()
+        && args.deny_chars.is_none()
+    {
+        return Ok(None);
+    }
+
+    let mut filter = CharFilter::new();
+    for script in &args.scripts {
+        filter = filter.script(script)?;
+    }
+    for category in &args.categories {
+        filter = filter.category(category)?;
+    }
+    if let Some(chars) = &args.allow_chars {
+        filter = filter.allow(chars);
+    }
+    if let Some(chars) = &args.deny_chars {
+        filter = filter.deny(chars);
+    }
+    Ok(Some(filter))
+}
+
+fn datetime_spec(format: &str, args: &GenerateArgs) -> regex_data_gen_core::Result<DateTimeSpec> {
+    let mut spec = DateTimeSpec::new(format.parse()?);
+    if args.start.is_some() || args.end.is_some() {
+        // A missing end keeps the default range's end
+        let start = match &args.start {
+            Some(start) => start.parse()?,
+            None => spec.start_bound(),
+        };
+        let end = match &args.end {
+            Some(end) => end.parse()?,
+            None => spec.end_bound(),
+        };
+        spec = spec.between(start, end)?;
+    }
+    if let Some(offset) = &args.utc_offset {
+        spec = spec.with_offset(parse_offset(offset)?);
+    }
+    Ok(spec)
+}
+
+async fn generate_records(args: &GenerateArgs, schema_path: &Path) -> anyhow::Result<()> {
+    let schema = match SchemaDocument::from_file(schema_path) {
+        Ok(SchemaDocument::Table(schema)) => schema,
+        Ok(SchemaDocument::Database(schema)) => {
+            return generate_database(args, schema_path, &schema).await;
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
         }
-        OutputFormat::Json => {
-            let exporter = JsonExporter::new();
-            exporter.export(&data, &output_path)
+    };
+
+    let generator = match args.seed {
+        Some(seed) => {
+            println!("ℹ️  Using seed: {} for reproducible generation", seed);
+            RecordGenerator::with_seed(&schema, seed)
+        }
+        None => RecordGenerator::from_schema(&schema),
+    };
+    let mut generator = match generator {
+        Ok(generator) => generator,
+        Err(e) => {
+            eprintln!("❌ Failed to create generator: {}", e);
+            std::process::exit(1);
         }
-        OutputFormat::Xml => {
-            let exporter = XmlExporter::new();
-            exporter.export(&data, &output_path)
+    };
+
+    let count = args
+        .count
+        .or(generator.default_count())
+        .unwrap_or(DEFAULT_COUNT);
+    println!(
+        "🔄 Generating {} records with {} fields from schema: '{}'",
+        count,
+        schema.fields.len(),
+        schema_path.display()
+    );
+
+    let records = match generator.generate(count) {
+        Ok(records) => {
+            println!("✅ Successfully generated {} records", records.len());
+            records
         }
-        OutputFormat::Tsv => {
-            let exporter = TsvExporter::new();
-            exporter.export(&data, &output_path)
+        Err(e) => {
+            eprintln!("❌ Data generation failed: {}", e);
+            std::process::exit(1);
         }
     };
 
-    match export_result {
+    let output_path = args.output.to_string_lossy();
+    println!(
+        "💾 Exporting to {} format...",
+        args.format.to_string().to_uppercase()
+    );
+
+    let table = TableInfo {
+        name: table_name(args),
+        max_lengths: records
+            .columns()
+            .iter()
+            .filter_map(|column| Some((column.clone(), generator.max_length(column)?)))
+            .collect(),
+        unique_columns: Vec::new(),
+    };
+    let exporter = create_exporter(&args.format, args, &table);
+    match exporter.export_records(&records, &output_path) {
         Ok(()) => {
             println!(
-                "🎉 Successfully exported {} items to '{}'",
-                args.count, output_path
+                "🎉 Successfully exported {} records to '{}'",
+                records.len(),
+                output_path
             );
         }
         Err(e) => {
@@ -183,8 +954,228 @@ async fn generate_data(args: GenerateArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn generate_database(
+    args: &GenerateArgs,
+    schema_path: &Path,
+    schema: &DatabaseSchema,
+) -> anyhow::Result<()> {
+    let generator = match args.seed {
+        Some(seed) => {
+            println!("ℹ️  Using seed: {} for reproducible generation", seed);
+            DatabaseGenerator::with_seed(schema, seed)
+        }
+        None => DatabaseGenerator::from_schema(schema),
+    };
+    let mut generator = match generator {
+        Ok(generator) => generator,
+        Err(e) => {
+            eprintln!("❌ Failed to create generator: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    println!(
+        "🔄 Generating {} tables from schema: '{}'",
+        schema.tables.len(),
+        schema_path.display()
+    );
+
+    let tables = match generator.generate() {
+        Ok(tables) => tables,
+        Err(e) => {
+            eprintln!("❌ Data generation failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = std::fs::create_dir_all(&args.output) {
+        eprintln!("❌ Failed to create '{}': {}", args.output.display(), e);
+        std::process::exit(1);
+    }
+
+    for table in &tables {
+        // Tables without an explicit output go to <output>/<name>.<format>
+        let (path, format) = match &table.output {
+            Some(output) => {
+                let path = PathBuf::from(output);
+                let format = OutputFormat::from_extension(&path).unwrap_or(args.format.clone());
+                (path, format)
+            }
+            None => (
+                args.output.join(format!("{}.{}", table.name, args.format)),
+                args.format.clone(),
+            ),
+        };
+
+        let output_path = path.to_string_lossy();
+        let info = TableInfo {
+            name: table.name.clone(),
+            max_lengths: table
+                .records
+                .columns()
+                .iter()
+                .filter_map(|column| {
+                    Some((column.clone(), generator.max_length(&table.name, column)?))
+                })
+                .collect(),
+            unique_columns: Vec::new(),
+        };
+        match create_exporter(&format, args, &info).export_records(&table.records, &output_path) {
+            Ok(()) => {
+                println!(
+                    "🎉 Exported {} records of '{}' to '{}'",
+                    table.records.len(),
+                    table.name,
+                    output_path
+                );
+            }
+            Err(e) => {
+                eprintln!("❌ Export of '{}' failed: {}", table.name, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// What the SQL, SQLite and fixed-width exporters need to know about the table being exported
+struct TableInfo {
+    name: String,
+    /// Known maximum lengths of columns, in characters
+    max_lengths: Vec<(String, usize)>,
+    /// Columns whose values are all distinct, indexed as UNIQUE in SQLite
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    unique_columns: Vec<String>,
+}
+
+/// SQL or SQLite table name: `--table`, or else the output file name
+fn table_name(args: &GenerateArgs) -> String {
+    args.table.clone().unwrap_or_else(|| {
+        args.output
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "generated_data".to_string())
+    })
+}
+
+fn create_exporter(
+    format: &OutputFormat,
+    args: &GenerateArgs,
+    table: &TableInfo,
+) -> Box<dyn Exporter> {
+    match format {
+        OutputFormat::Sql => {
+            let mut exporter = SqlExporter::new(args.dialect.into())
+                .with_table(&table.name)
+                .with_batch_size(args.batch_size);
+            if args.create_table {
+                exporter = exporter.with_create_table();
+                for (column, length) in &table.max_lengths {
+                    exporter = exporter.with_max_length(column, *length);
+                }
+            }
+            Box::new(exporter)
+        }
+        #[cfg(feature = "sqlite")]
+        OutputFormat::Sqlite => {
+            let mut exporter = SqliteExporter::new().with_table(&table.name);
+            for column in &table.unique_columns {
+                exporter = exporter.with_unique_index(column);
+            }
+            Box::new(exporter)
+        }
+        OutputFormat::Csv => Box::new(CsvExporter::new()),
+        OutputFormat::Json => Box::new(JsonExporter::new()),
+        OutputFormat::Jsonl => Box::new(JsonLinesExporter::new()),
+        OutputFormat::Xml => Box::new(XmlExporter::new()),
+        OutputFormat::Tsv => Box::new(TsvExporter::new()),
+        #[cfg(feature = "parquet")]
+        OutputFormat::Parquet => Box::new(parquet_exporter(args)),
+        #[cfg(feature = "arrow")]
+        OutputFormat::Arrow | OutputFormat::ArrowStream => Box::new(arrow_exporter(format, args)),
+        #[cfg(feature = "avro")]
+        OutputFormat::Avro => Box::new(avro_exporter(args)),
+        #[cfg(feature = "msgpack")]
+        OutputFormat::Msgpack => Box::new(MessagePackExporter::new()),
+        #[cfg(feature = "cbor")]
+        OutputFormat::Cbor => Box::new(CborExporter::new()),
+        #[cfg(feature = "bson")]
+        OutputFormat::Bson => Box::new(BsonExporter::new()),
+        OutputFormat::Yaml => Box::new(YamlExporter::with_layout(args.layout.into())),
+        OutputFormat::Toml => Box::new(TomlExporter::with_layout(args.layout.into())),
+        OutputFormat::Fixed => Box::new(fixed_width_exporter(args, table)),
+        OutputFormat::Markdown => Box::new(MarkdownExporter::new()),
+        OutputFormat::Html => Box::new(HtmlExporter::new()),
+    }
+}
+
+fn fixed_width_exporter(args: &GenerateArgs, table: &TableInfo) -> FixedWidthExporter {
+    let mut exporter = FixedWidthExporter::new()
+        .with_alignment(args.align.into())
+        .with_pad_char(args.pad_char);
+    if args.truncate {
+        exporter = exporter.with_truncation();
+    }
+    if let Some(width) = args.width {
+        return exporter.with_default_width(width);
+    }
+    for (column, length) in &table.max_lengths {
+        exporter = exporter.with_width(column, *length);
+    }
+    exporter
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_exporter(args: &GenerateArgs) -> ParquetExporter {
+    let compression = match args.compression {
+        None | Some(CompressionArg::Snappy) => ParquetCompression::Snappy,
+        Some(CompressionArg::Zstd) => ParquetCompression::Zstd,
+        Some(compression) => unsupported_compression(compression, "Parquet"),
+    };
+    ParquetExporter::new()
+        .with_row_group_size(args.row_group_size)
+        .with_compression(compression)
+}
+
+#[cfg(feature = "avro")]
+fn avro_exporter(args: &GenerateArgs) -> AvroExporter {
+    let compression = match args.compression {
+        None | Some(CompressionArg::None) => AvroCompression::None,
+        Some(CompressionArg::Snappy) => AvroCompression::Snappy,
+        Some(CompressionArg::Deflate) => AvroCompression::Deflate,
+        Some(compression) => unsupported_compression(compression, "Avro"),
+    };
+    AvroExporter::new().with_compression(compression)
+}
+
+#[cfg(any(feature = "parquet", feature = "avro"))]
+fn unsupported_compression(compression: CompressionArg, format: &str) -> ! {
+    eprintln!(
+        "❌ {} output does not support {} compression",
+        format, compression
+    );
+    std::process::exit(1);
+}
+
+#[cfg(feature = "arrow")]
+fn arrow_exporter(format: &OutputFormat, args: &GenerateArgs) -> ArrowIpcExporter {
+    let format = match format {
+        OutputFormat::ArrowStream => ArrowIpcFormat::Stream,
+        _ => ArrowIpcFormat::File,
+    };
+    let exporter = ArrowIpcExporter::new()
+        .with_format(format)
+        .with_batch_size(args.row_group_size);
+    if args.large_utf8 {
+        exporter.with_large_utf8()
+    } else {
+        exporter
+    }
+}
+
 async fn validate_pattern(args: ValidateArgs) -> anyhow::Result<()> {
-    match regex_data_gen_core::RegexEngine::validate_pattern(&args.pattern) {
+    match RegexEngine::validate_pattern(&args.pattern) {
         Ok(()) => {
             println!("✓ Pattern '{}' is valid", args.pattern);
         }
@@ -195,3 +1186,169 @@ async fn validate_pattern(args: ValidateArgs) -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+async fn list_patterns() -> anyhow::Result<()> {
+    println!("📚 Built-in patterns:");
+    for named in NamedPattern::all() {
+        println!("  @{:<14} {}", named.name, named.description);
+        println!("  {:<15} {}", "", named.pattern);
+    }
+    Ok(())
+}
+
+async fn infer_pattern(args: InferArgs) -> anyhow::Result<()> {
+    let mut samples = args.samples;
+
+    if let Some(input) = &args.input {
+        match std::fs::read_to_string(input) {
+            Ok(content) => samples.extend(content.lines().map(str::to_string)),
+            Err(e) => {
+                eprintln!("❌ Failed to read '{}': {}", input.display(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    match RegexEngine::infer_from_samples(&samples) {
+        Ok(pattern) => {
+            eprintln!("🔍 Inferred pattern from {} samples:", samples.len());
+            println!("{}", pattern);
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+async fn diff_patterns(args: DiffArgs) -> anyhow::Result<()> {
+    let result = RegexEngine::new(&args.old)
+        .and_then(|old| RegexEngine::new(&args.new).map(|new| (old, new)))
+        .and_then(|(old, new)| old.diff(&new, args.limit));
+
+    let diff = match result {
+        Ok(diff) => diff,
+        Err(e) => {
+            eprintln!("❌ Failed to compare patterns: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if diff.is_equivalent() {
+        println!("✓ Patterns accept the same strings");
+        return Ok(());
+    }
+
+    if diff.is_subset() {
+        println!("⊂ Old pattern accepts a subset of the new pattern");
+    } else if diff.is_superset() {
+        println!("⊃ Old pattern accepts a superset of the new pattern");
+    } else {
+        println!("≠ Patterns accept different strings");
+    }
+
+    if !diff.only_left.is_empty() {
+        println!("➖ Accepted by old only (no longer valid):");
+        for witness in &diff.only_left {
+            println!("  {:?}", witness);
+        }
+    }
+
+    if !diff.only_right.is_empty() {
+        println!("➕ Accepted by new only (newly valid):");
+        for witness in &diff.only_right {
+            println!("  {:?}", witness);
+        }
+    }
+
+    Ok(())
+}
+
+async fn check_dataset(args: CheckArgs) -> anyhow::Result<()> {
+    let engine = args
+        .ranges
+        .iter()
+        .fold(RegexEngine::new(&args.pattern), |engine, (group, range)| {
+            engine.and_then(|e| e.with_range(group, range.clone()))
+        });
+    let engine = match engine {
+        Ok(engine) => engine,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let Some(format) = args
+        .format
+        .or_else(|| OutputFormat::from_extension(&args.input))
+    else {
+        eprintln!(
+            "❌ Cannot infer the format of '{}', use --format",
+            args.input.display()
+        );
+        std::process::exit(1);
+    };
+
+    let importer: Box<dyn Importer> = match (format, args.column) {
+        (OutputFormat::Csv, Some(column)) => Box::new(CsvImporter::with_column(column)),
+        (OutputFormat::Csv, None) => Box::new(CsvImporter::new()),
+        (OutputFormat::Json, Some(field)) => Box::new(JsonImporter::with_field(field)),
+        (OutputFormat::Json, None) => Box::new(JsonImporter::new()),
+        (OutputFormat::Xml, Some(element)) => Box::new(XmlImporter::with_element(element)),
+        (OutputFormat::Xml, None) => Box::new(XmlImporter::new()),
+        (OutputFormat::Tsv, Some(column)) => Box::new(TsvImporter::with_column(column)),
+        (OutputFormat::Tsv, None) => Box::new(TsvImporter::new()),
+        (format, _) => {
+            eprintln!(
+                "❌ Cannot check {} files, use csv, json, xml or tsv",
+                format
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let input_path = args.input.to_string_lossy();
+    let values = match importer.import(&input_path) {
+        Ok(values) => values,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    println!(
+        "🔍 Checking {} {} values from '{}' against pattern: '{}'",
+        values.len(),
+        importer.format_name(),
+        input_path,
+        args.pattern
+    );
+
+    let failures: Vec<_> = values
+        .iter()
+        .filter(|v| !engine.is_match(&v.value))
+        .collect();
+    let matched = values.len() - failures.len();
+    let rate = if values.is_empty() {
+        100.0
+    } else {
+        matched as f64 / values.len() as f64 * 100.0
+    };
+    println!("📊 Match rate: {:.2}% ({}/{})", rate, matched, values.len());
+
+    if failures.is_empty() {
+        println!("✅ All values match");
+        return Ok(());
+    }
+
+    println!("❌ {} values do not match:", failures.len());
+    for failure in failures.iter().take(args.show) {
+        println!("  line {}: {:?}", failure.line, failure.value);
+    }
+    if failures.len() > args.show {
+        println!("  ... and {} more", failures.len() - args.show);
+    }
+    std::process::exit(1);
+}
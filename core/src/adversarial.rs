@@ -0,0 +1,234 @@
+use crate::rewrite;
+use regex_syntax::ParserBuilder;
+use regex_syntax::hir::{Capture, Class, ClassUnicode, ClassUnicodeRange, Hir, HirKind};
+use std::fmt;
+use std::sync::LazyLock;
+
+/// Characters that look like ASCII letters, digits or punctuation
+const HOMOGLYPHS: &str = "АВЕКМНОРСТХаеорсухіјѕԁԛԝӏ\
+                          ΑΒΕΖΗΙΚΜΝΟΡΤΥΧοανιρ\
+                          ıɑɡſKÅ‐‑‒–—․∕⁄";
+
+/// Character classes of the tricks, in the order of `Trick::all`
+static CLASSES: LazyLock<Vec<ClassUnicode>> =
+    LazyLock::new(|| Trick::all().iter().map(Trick::build_class).collect());
+
+/// Ways a character can trip up code that handles text
+///
+/// Every tricky character is also more than one byte long in UTF-8, so
+/// `MultiByte` is only reported for characters with no other trick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trick {
+    /// Combines with the previous character, as U+0301 combining acute accent
+    CombiningMark,
+    /// Zero-width space, joiner or non-joiner, word joiner or byte order mark
+    ZeroWidth,
+    /// Bidirectional override, embedding, isolate or mark, as U+202E
+    BidiControl,
+    /// Outside the Basic Multilingual Plane, such as most emoji; a UTF-16
+    /// surrogate pair
+    Astral,
+    /// Looks like an ASCII character, as Cyrillic `а` or fullwidth `Ａ`
+    Homoglyph,
+    /// UTF-8 byte length differs from its char count
+    MultiByte,
+}
+
+impl Trick {
+    /// Every trick, from the most to the least specific
+    pub fn all() -> &'static [Trick] {
+        &[
+            Trick::CombiningMark,
+            Trick::ZeroWidth,
+            Trick::BidiControl,
+            Trick::Astral,
+            Trick::Homoglyph,
+            Trick::MultiByte,
+        ]
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Trick::CombiningMark => "combining mark",
+            Trick::ZeroWidth => "zero-width character",
+            Trick::BidiControl => "bidirectional control",
+            Trick::Astral => "astral-plane character",
+            Trick::Homoglyph => "homoglyph of an ASCII character",
+            Trick::MultiByte => "multi-byte character",
+        }
+    }
+
+    /// Whether `c` has this trick
+    pub fn matches(&self, c: char) -> bool {
+        self.class()
+            .ranges()
+            .iter()
+            .any(|r| r.start() <= c && c <= r.end())
+    }
+
+    fn class(&self) -> &'static ClassUnicode {
+        &CLASSES[*self as usize]
+    }
+
+    fn build_class(&self) -> ClassUnicode {
+        match self {
+            Trick::CombiningMark => property_class(r"\p{M}"),
+            Trick::ZeroWidth => chars_class("\u{200B}\u{200C}\u{200D}\u{2060}\u{FEFF}"),
+            Trick::BidiControl => ClassUnicode::new([
+                ClassUnicodeRange::new('\u{061C}', '\u{061C}'),
+                ClassUnicodeRange::new('\u{200E}', '\u{200F}'),
+                ClassUnicodeRange::new('\u{202A}', '\u{202E}'),
+                ClassUnicodeRange::new('\u{2066}', '\u{2069}'),
+            ]),
+            Trick::Astral => ClassUnicode::new([ClassUnicodeRange::new('\u{10000}', char::MAX)]),
+            Trick::Homoglyph => {
+                let mut class = chars_class(HOMOGLYPHS);
+                // Fullwidth forms of ASCII
+                class.union(&ClassUnicode::new([ClassUnicodeRange::new(
+                    '\u{FF01}', '\u{FF5E}',
+                )]));
+                class
+            }
+            Trick::MultiByte => ClassUnicode::new([ClassUnicodeRange::new('\u{80}', char::MAX)]),
+        }
+    }
+}
+
+impl fmt::Display for Trick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// A character of a value and the tricks it plays
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrickyChar {
+    /// Position of the character in the value, counted in chars
+    pub position: usize,
+    pub character: char,
+    pub tricks: Vec<Trick>,
+}
+
+impl fmt::Display for TrickyChar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tricks: Vec<&str> = self.tricks.iter().map(Trick::description).collect();
+        write!(
+            f,
+            "U+{:04X} at {}: {}",
+            self.character as u32,
+            self.position,
+            tricks.join(", ")
+        )
+    }
+}
+
+/// Explain why `value` is tricky, one entry per tricky character
+pub fn explain(value: &str) -> Vec<TrickyChar> {
+    value
+        .chars()
+        .enumerate()
+        .filter_map(|(position, character)| {
+            let mut tricks: Vec<Trick> = Trick::all()
+                .iter()
+                .copied()
+                .filter(|trick| *trick != Trick::MultiByte && trick.matches(character))
+                .collect();
+            if tricks.is_empty() && character.len_utf8() > 1 {
+                tricks.push(Trick::MultiByte);
+            }
+            (!tricks.is_empty()).then_some(TrickyChar {
+                position,
+                character,
+                tricks,
+            })
+        })
+        .collect()
+}
+
+/// Narrow every character class of `hir` to its tricky characters
+///
+/// Each class becomes an alternation with one branch per trick it allows, so
+/// that rare tricks such as zero-width characters are drawn as often as
+/// astral characters. Classes without tricky characters are kept. Returns the
+/// tricks the rewritten pattern can generate.
+pub(crate) fn bias(hir: Hir) -> (Hir, Vec<Trick>) {
+    let mut available = Vec::new();
+    let hir = rewrite::map_classes(hir, &mut |class| narrow(class, &mut available));
+    (hir, available)
+}
+
+fn narrow(class: Class, available: &mut Vec<Trick>) -> Hir {
+    let Class::Unicode(class) = class else {
+        return Hir::class(class);
+    };
+    let mut branches: Vec<(Trick, ClassUnicode)> = Trick::all()
+        .iter()
+        .map(|trick| {
+            let mut narrowed = class.clone();
+            narrowed.intersect(trick.class());
+            if *trick == Trick::Astral {
+                narrowed = prefer_assigned(narrowed);
+            }
+            (*trick, narrowed)
+        })
+        .filter(|(_, narrowed)| !narrowed.ranges().is_empty())
+        .collect();
+    // Any multi-byte character is better than none, but it is only a
+    // branch of its own when nothing more specific is allowed
+    if branches.len() > 1 {
+        branches.retain(|(trick, _)| *trick != Trick::MultiByte);
+    }
+    if branches.is_empty() {
+        return Hir::class(Class::Unicode(class));
+    }
+
+    for (trick, _) in &branches {
+        if !available.contains(trick) {
+            available.push(*trick);
+        }
+    }
+    // Each branch sits in its own group, as an alternation of bare
+    // classes would be merged back into a single class
+    Hir::alternation(
+        branches
+            .into_iter()
+            .map(|(_, narrowed)| {
+                Hir::capture(Capture {
+                    index: 0,
+                    name: None,
+                    sub: Box::new(Hir::class(Class::Unicode(narrowed))),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Narrow astral characters to emoji, or else to assigned characters
+///
+/// Most of the astral planes are unassigned, so `.` would otherwise almost
+/// never yield an emoji.
+fn prefer_assigned(class: ClassUnicode) -> ClassUnicode {
+    for property in [r"\p{Extended_Pictographic}", r"[^\p{Cn}\p{Co}]"] {
+        let mut preferred = class.clone();
+        preferred.intersect(&property_class(property));
+        if !preferred.ranges().is_empty() {
+            return preferred;
+        }
+    }
+    class
+}
+
+fn property_class(property: &str) -> ClassUnicode {
+    match ParserBuilder::new()
+        .build()
+        .parse(property)
+        .map(Hir::into_kind)
+    {
+        Ok(HirKind::Class(Class::Unicode(class))) => class,
+        _ => ClassUnicode::empty(),
+    }
+}
+
+fn chars_class(chars: &str) -> ClassUnicode {
+    ClassUnicode::new(chars.chars().map(|c| ClassUnicodeRange::new(c, c)))
+}
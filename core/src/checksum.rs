@@ -0,0 +1,235 @@
+use serde::Deserialize;
+
+/// Check digit schemes that can be applied to generated values
+///
+/// Patterns describe the shape of identifiers but not their check digits, so
+/// a checksum is applied after generation. Only ASCII digits (and letters for
+/// IBAN) count towards the checksum; separators such as `-` or spaces are
+/// kept as generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Checksum {
+    /// Luhn mod 10, used by payment card numbers; any number of digits
+    Luhn,
+    /// ISBN-10 mod 11: nine digits and a check digit, `X` standing for 10
+    Isbn10,
+    /// ISBN-13: twelve digits and an EAN-13 check digit
+    Isbn13,
+    /// IBAN mod 97: the third and fourth characters are the check digits
+    Iban,
+    /// EAN-8: seven digits and a check digit
+    Ean8,
+    /// EAN-13: twelve digits and a check digit
+    Ean13,
+}
+
+impl Checksum {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Checksum::Luhn => "Luhn",
+            Checksum::Isbn10 => "ISBN-10",
+            Checksum::Isbn13 => "ISBN-13",
+            Checksum::Iban => "IBAN",
+            Checksum::Ean8 => "EAN-8",
+            Checksum::Ean13 => "EAN-13",
+        }
+    }
+
+    /// Whether `value` already carries a correct check digit
+    pub fn is_valid(&self, value: &str) -> bool {
+        self.fix(value).as_deref() == Some(value)
+    }
+
+    /// Replace the check digit of `value`, which is assumed to be its last
+    /// digit (or the third and fourth characters for IBAN)
+    pub fn fix(&self, value: &str) -> Option<String> {
+        match self {
+            Checksum::Luhn => {
+                let positions = digit_positions(value, false);
+                let (&last, payload) = positions.split_last()?;
+                if payload.is_empty() {
+                    return None;
+                }
+                let check = luhn_digit(&digits_at(value, payload)?);
+                Some(replace_at(value, &[last], &check.to_string()))
+            }
+            Checksum::Isbn10 | Checksum::Isbn13 | Checksum::Ean8 | Checksum::Ean13 => {
+                let positions = digit_positions(value, *self == Checksum::Isbn10);
+                if positions.len() != self.length()? {
+                    return None;
+                }
+                let (&last, payload) = positions.split_last()?;
+                let check = self.check_character(&digits_at(value, payload)?);
+                Some(replace_at(value, &[last], &check))
+            }
+            Checksum::Iban => {
+                let positions: Vec<usize> = value
+                    .char_indices()
+                    .filter(|(_, c)| c.is_ascii_alphanumeric())
+                    .map(|(i, _)| i)
+                    .collect();
+                if positions.len() < 5 {
+                    return None;
+                }
+                let compact: String = positions
+                    .iter()
+                    .map(|&i| value.as_bytes()[i].to_ascii_uppercase() as char)
+                    .collect();
+                let check = iban_check_digits(&compact)?;
+                Some(replace_at(value, &positions[2..4], &check))
+            }
+        }
+    }
+
+    /// Append a check digit computed over every digit of `value`
+    ///
+    /// Fixed-length schemes only append to values that are exactly one digit
+    /// short; IBAN check digits cannot be appended.
+    pub fn append(&self, value: &str) -> Option<String> {
+        let positions = digit_positions(value, false);
+        let digits = digits_at(value, &positions)?;
+        match self {
+            Checksum::Luhn if !digits.is_empty() => {
+                Some(format!("{}{}", value, luhn_digit(&digits)))
+            }
+            Checksum::Isbn10 | Checksum::Isbn13 | Checksum::Ean8 | Checksum::Ean13
+                if Some(digits.len() + 1) == self.length() =>
+            {
+                Some(format!("{}{}", value, self.check_character(&digits)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Ways to give `value` a valid check digit, preferring to fix it in place
+    pub(crate) fn candidates(&self, value: &str) -> Vec<String> {
+        [self.fix(value), self.append(value)]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Total number of digits, for fixed-length schemes
+    fn length(&self) -> Option<usize> {
+        match self {
+            Checksum::Isbn10 => Some(10),
+            Checksum::Isbn13 | Checksum::Ean13 => Some(13),
+            Checksum::Ean8 => Some(8),
+            Checksum::Luhn | Checksum::Iban => None,
+        }
+    }
+
+    fn check_character(&self, payload: &[u32]) -> String {
+        match self {
+            Checksum::Isbn10 => {
+                let sum: u32 = payload
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &d)| (10 - i as u32) * d)
+                    .sum();
+                match (11 - sum % 11) % 11 {
+                    10 => "X".to_string(),
+                    check => check.to_string(),
+                }
+            }
+            _ => gtin_digit(payload).to_string(),
+        }
+    }
+}
+
+/// Luhn check digit for the ASCII digits of `value`
+pub(crate) fn luhn_check_digit(value: &str) -> Option<u32> {
+    let digits: Vec<u32> = value.chars().filter_map(|c| c.to_digit(10)).collect();
+    if digits.is_empty() {
+        return None;
+    }
+    Some(luhn_digit(&digits))
+}
+
+fn luhn_digit(payload: &[u32]) -> u32 {
+    // Double every second digit counting from the right, starting with the last
+    let sum: u32 = payload
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 0 {
+                let doubled = d * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                d
+            }
+        })
+        .sum();
+
+    (10 - sum % 10) % 10
+}
+
+/// EAN/GTIN check digit: weights 3 and 1 alternating from the right
+fn gtin_digit(payload: &[u32]) -> u32 {
+    let sum: u32 = payload
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| if i % 2 == 0 { d * 3 } else { d })
+        .sum();
+
+    (10 - sum % 10) % 10
+}
+
+/// Check digits for an uppercase, separator-free IBAN
+fn iban_check_digits(compact: &str) -> Option<String> {
+    let (country, rest) = compact.split_at(2);
+    if !country.bytes().all(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+
+    // Move the country code and zeroed check digits to the end, then read
+    // letters as numbers from 10 to 35
+    let rearranged = format!("{}{}00", &rest[2..], country);
+    let mut remainder = 0u32;
+    for c in rearranged.chars() {
+        let value = c.to_digit(36)?;
+        remainder = if value < 10 {
+            (remainder * 10 + value) % 97
+        } else {
+            (remainder * 100 + value) % 97
+        };
+    }
+
+    Some(format!("{:02}", 98 - remainder))
+}
+
+/// Byte offsets of the ASCII digits of `value`, and of a trailing `X` when
+/// `allow_x` is set
+fn digit_positions(value: &str, allow_x: bool) -> Vec<usize> {
+    let last = value.trim_end().len().saturating_sub(1);
+    value
+        .char_indices()
+        .filter(|&(i, c)| {
+            c.is_ascii_digit() || (allow_x && i == last && c.eq_ignore_ascii_case(&'x'))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Digits at `positions`, or `None` when one of them is not a digit
+fn digits_at(value: &str, positions: &[usize]) -> Option<Vec<u32>> {
+    positions
+        .iter()
+        .map(|&i| (value.as_bytes()[i] as char).to_digit(10))
+        .collect()
+}
+
+/// Overwrite the single-byte characters at `positions` with `replacement`
+fn replace_at(value: &str, positions: &[usize], replacement: &str) -> String {
+    let mut bytes = value.as_bytes().to_vec();
+    let mut replacement = replacement.bytes();
+    for &i in positions {
+        if let Some(byte) = replacement.next() {
+            bytes[i] = byte;
+        }
+    }
+    // Only ASCII bytes are replaced by ASCII bytes, so the result stays UTF-8
+    String::from_utf8(bytes).unwrap_or_else(|_| value.to_string())
+}
@@ -0,0 +1,233 @@
+use crate::language::Language;
+use crate::{Error, Result};
+use rand::Rng;
+use rand::rngs::StdRng;
+use regex_automata::util::primitives::StateID;
+use std::collections::{HashMap, VecDeque};
+
+/// Upper bound on product states before falling back to rejection sampling
+const MAX_PRODUCT_STATES: usize = 100_000;
+
+/// Extra characters a walk may take beyond the shortest accepted string
+const MAX_EXTRA_LENGTH: usize = 100;
+
+/// Attempts per value when falling back to rejection sampling
+const MAX_REJECTION_ATTEMPTS: usize = 10_000;
+
+/// How several patterns are combined into one generator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombinationRule {
+    /// Strings matching every pattern
+    All,
+    /// Strings matching the first pattern but none of the others
+    FirstExceptRest,
+}
+
+/// Samples strings from the product of several pattern automata
+pub(crate) struct CombinedSampler {
+    languages: Vec<Language>,
+    rule: CombinationRule,
+    graph: Option<ProductGraph>,
+}
+
+/// Explored product automaton with distances to the nearest accepting state
+struct ProductGraph {
+    /// Successors of each state, grouped by target state
+    edges: Vec<Vec<(usize, Vec<u8>)>>,
+    accepting: Vec<bool>,
+    distance: Vec<usize>,
+}
+
+impl CombinedSampler {
+    pub(crate) fn new(patterns: &[&str], rule: CombinationRule) -> Result<Self> {
+        if patterns.is_empty() {
+            return Err(Error::InvalidRegex(
+                "At least one pattern is required".to_string(),
+            ));
+        }
+
+        let languages = patterns
+            .iter()
+            .map(|pattern| Language::from_pattern(pattern))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut sampler = Self {
+            languages,
+            rule,
+            graph: None,
+        };
+        sampler.graph = sampler.explore();
+
+        if let Some(graph) = &sampler.graph
+            && graph.distance[0] == usize::MAX
+        {
+            return Err(Error::GenerationFailed(
+                "No string satisfies every pattern in the combination".to_string(),
+            ));
+        }
+
+        Ok(sampler)
+    }
+
+    /// Generate one string, walking the product automaton when it could be
+    /// explored and rejection sampling from `fallback` otherwise
+    pub(crate) fn sample(&self, rng: &mut StdRng, fallback: &rand_regex::Regex) -> Result<String> {
+        match &self.graph {
+            Some(graph) => self.walk(graph, rng),
+            None => self.reject(rng, fallback),
+        }
+    }
+
+    fn is_positive(&self, index: usize) -> bool {
+        match self.rule {
+            CombinationRule::All => true,
+            CombinationRule::FirstExceptRest => index == 0,
+        }
+    }
+
+    fn accepts(&self, states: &[StateID]) -> bool {
+        states
+            .iter()
+            .enumerate()
+            .all(|(i, &state)| self.languages[i].is_accepting(state) == self.is_positive(i))
+    }
+
+    fn explore(&self) -> Option<ProductGraph> {
+        let start: Vec<StateID> = self.languages.iter().map(Language::start).collect();
+
+        let mut ids: HashMap<Vec<StateID>, usize> = HashMap::new();
+        let mut states = vec![start.clone()];
+        let mut edges: Vec<Vec<(usize, Vec<u8>)>> = vec![Vec::new()];
+        let mut queue = VecDeque::from([0]);
+        ids.insert(start, 0);
+
+        while let Some(index) = queue.pop_front() {
+            let mut grouped: Vec<(usize, Vec<u8>)> = Vec::new();
+
+            for byte in 0..=u8::MAX {
+                let next: Vec<StateID> = states[index]
+                    .iter()
+                    .zip(&self.languages)
+                    .map(|(&state, language)| language.next(state, byte))
+                    .collect();
+
+                let positive_dead = next
+                    .iter()
+                    .zip(&self.languages)
+                    .enumerate()
+                    .any(|(i, (&state, language))| self.is_positive(i) && language.is_dead(state));
+                if positive_dead {
+                    continue;
+                }
+
+                let target = match ids.get(&next) {
+                    Some(&id) => id,
+                    None => {
+                        if states.len() >= MAX_PRODUCT_STATES {
+                            return None;
+                        }
+                        let id = states.len();
+                        ids.insert(next.clone(), id);
+                        states.push(next);
+                        edges.push(Vec::new());
+                        queue.push_back(id);
+                        id
+                    }
+                };
+
+                match grouped.iter_mut().find(|(id, _)| *id == target) {
+                    Some((_, bytes)) => bytes.push(byte),
+                    None => grouped.push((target, vec![byte])),
+                }
+            }
+
+            edges[index] = grouped;
+        }
+
+        let accepting: Vec<bool> = states.iter().map(|s| self.accepts(s)).collect();
+        let distance = distances(&edges, &accepting);
+
+        Some(ProductGraph {
+            edges,
+            accepting,
+            distance,
+        })
+    }
+
+    fn walk(&self, graph: &ProductGraph, rng: &mut StdRng) -> Result<String> {
+        let mut bytes = Vec::new();
+        let mut state = 0;
+        let mut budget = graph.distance[0] + MAX_EXTRA_LENGTH;
+
+        loop {
+            // Only successors that can still reach an accepting state in time
+            let options: Vec<&(usize, Vec<u8>)> = graph.edges[state]
+                .iter()
+                .filter(|(next, _)| budget > 0 && graph.distance[*next] < budget)
+                .collect();
+
+            let can_stop = graph.accepting[state];
+            let choice = rng.random_range(0..options.len() + usize::from(can_stop));
+            if choice == options.len() {
+                break;
+            }
+
+            let (next, choices) = options[choice];
+            bytes.push(choices[rng.random_range(0..choices.len())]);
+            state = *next;
+            budget -= 1;
+        }
+
+        String::from_utf8(bytes)
+            .map_err(|e| Error::GenerationFailed(format!("Generated invalid UTF-8: {}", e)))
+    }
+
+    fn reject(&self, rng: &mut StdRng, fallback: &rand_regex::Regex) -> Result<String> {
+        for _ in 0..MAX_REJECTION_ATTEMPTS {
+            let candidate: String = rng.sample(fallback);
+            let matches = self
+                .languages
+                .iter()
+                .enumerate()
+                .all(|(i, language)| language.accepts(&candidate) == self.is_positive(i));
+            if matches {
+                return Ok(candidate);
+            }
+        }
+
+        Err(Error::GenerationFailed(format!(
+            "No combined match found after {} attempts",
+            MAX_REJECTION_ATTEMPTS
+        )))
+    }
+}
+
+/// Shortest number of steps from each state to an accepting state
+fn distances(edges: &[Vec<(usize, Vec<u8>)>], accepting: &[bool]) -> Vec<usize> {
+    let mut reverse: Vec<Vec<usize>> = vec![Vec::new(); edges.len()];
+    for (from, targets) in edges.iter().enumerate() {
+        for (to, _) in targets {
+            reverse[*to].push(from);
+        }
+    }
+
+    let mut distance = vec![usize::MAX; edges.len()];
+    let mut queue = VecDeque::new();
+    for (state, &accepts) in accepting.iter().enumerate() {
+        if accepts {
+            distance[state] = 0;
+            queue.push_back(state);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        for &previous in &reverse[state] {
+            if distance[previous] == usize::MAX {
+                distance[previous] = distance[state] + 1;
+                queue.push_back(previous);
+            }
+        }
+    }
+
+    distance
+}
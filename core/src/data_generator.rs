@@ -1,49 +1,332 @@
-use crate::{RegexEngine, Result};
+use crate::adversarial::{self, Trick};
+use crate::checksum::Checksum;
+use crate::combination::{CombinationRule, CombinedSampler};
+use crate::datetime::DateTimeSpec;
+use crate::dictionary::{self, Dictionary};
+use crate::filter::CharFilter;
+use crate::numeric::{self, NumericRange};
+use crate::transform::Transform;
+use crate::{RegexEngine, Result, rewrite};
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng, rng};
 use regex::Regex;
+use regex_syntax::ParserBuilder;
+use serde::Deserialize;
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GenerationMode {
+    #[default]
     Random,
     Sequential,
+    #[serde(alias = "reverse")]
     ReverseSequential,
 }
 
+/// Attempts per value to find a post-processed value that still matches the pattern
+const MAX_POST_PROCESS_ATTEMPTS: usize = 1_000;
+
+/// Pattern used by generators that only produce dates and times
+pub const ANY_PATTERN: &str = "(?s:.*)";
+
 pub struct DataGenerator {
     regex_engine: RegexEngine,
     regex_generator: rand_regex::Regex,
     rng: StdRng,
     pattern: String,
+    /// `pattern` with library references and dictionaries expanded
+    expanded: String,
+    combined: Option<CombinedSampler>,
+    checksum: Option<Checksum>,
+    datetime: Option<DateTimeSpec>,
+    ranges: Vec<(String, NumericRange)>,
+    /// Characters marking where each range's number goes in sampled values
+    range_markers: Vec<char>,
+    dictionaries: Vec<(String, Dictionary)>,
+    /// Characters marking where an entry goes in sampled values, with the
+    /// index of the dictionary it is drawn from
+    dictionary_markers: Vec<(char, usize)>,
+    transforms: Vec<Transform>,
+    tricks: Vec<Trick>,
 }
 
-impl DataGenerator {
-    pub fn new(pattern: &str) -> Result<Self> {
-        let regex_engine = RegexEngine::new(pattern)?;
-        let regex_generator = rand_regex::Regex::compile(pattern, 100)
-            .map_err(|e| crate::Error::InvalidRegex(e.to_string()))?;
-        let mut thread_rng = rng();
-        let rng = StdRng::from_rng(&mut thread_rng);
+/// Configures a `DataGenerator` before building it
+///
+/// ```no_run
+/// use regex_data_gen_core::{Checksum, DataGenerator};
+///
+/// let mut generator = DataGenerator::builder("4[0-9]{15}")
+///     .seed(42)
+///     .checksum(Checksum::Luhn)
+///     .build()
+///     .unwrap();
+/// let card_numbers = generator.generate(10).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DataGeneratorBuilder {
+    pattern: String,
+    seed: Option<u64>,
+    ascii_only: bool,
+    checksum: Option<Checksum>,
+    datetime: Option<DateTimeSpec>,
+    ranges: Vec<(String, NumericRange)>,
+    dictionaries: Vec<(String, Dictionary)>,
+    transforms: Vec<Transform>,
+    filter: Option<CharFilter>,
+    adversarial: bool,
+}
 
-        Ok(Self {
+impl DataGeneratorBuilder {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            seed: None,
+            ascii_only: false,
+            checksum: None,
+            datetime: None,
+            ranges: Vec::new(),
+            dictionaries: Vec::new(),
+            transforms: Vec::new(),
+            filter: None,
+            adversarial: false,
+        }
+    }
+
+    /// Seed the random number generator for reproducible output
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Parse the pattern with Unicode disabled so only ASCII is generated
+    pub fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    /// Give every value a valid check digit
+    ///
+    /// The check digit replaces the value's last digit, or is appended when
+    /// the value is one digit short of the scheme's length. Values that the
+    /// whole pattern no longer matches afterwards are regenerated.
+    pub fn checksum(mut self, checksum: Checksum) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
+    /// Generate real calendar dates and times instead of sampling the pattern
+    ///
+    /// Dates that do not match the pattern are redrawn, so the pattern can
+    /// still constrain their shape, as `2024-0[1-6]-.*` does.
+    pub fn datetime(mut self, datetime: DateTimeSpec) -> Self {
+        self.datetime = Some(datetime);
+        self
+    }
+
+    /// Limit the named capture group `group` to the numbers of `range`
+    ///
+    /// Every occurrence of the group is replaced by a number from the range,
+    /// and values whose numbers no longer fit the rest of the pattern are
+    /// regenerated.
+    pub fn range(mut self, group: &str, range: NumericRange) -> Self {
+        self.ranges.push((group.to_string(), range));
+        self
+    }
+
+    /// Fill `{{dict:name}}` placeholders of the pattern from `dictionary`
+    ///
+    /// ```no_run
+    /// use regex_data_gen_core::{DataGenerator, Dictionary};
+    ///
+    /// let cities = Dictionary::from_file("cities.txt").unwrap();
+    /// let mut generator = DataGenerator::builder(r"(?P<city>{{dict:cities}}), [A-Z]{2} \d{5}")
+    ///     .dictionary("cities", cities)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn dictionary(mut self, name: &str, dictionary: Dictionary) -> Self {
+        self.dictionaries.push((name.to_string(), dictionary));
+        self
+    }
+
+    /// Rewrite every value with `transform`, after any transforms added before
+    ///
+    /// Transformed values that no longer match the pattern are regenerated.
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.transforms.push(transform);
+        self
+    }
+
+    /// Only generate characters that pass `filter` from character classes
+    ///
+    /// The pattern is still parsed with full Unicode semantics, so `\w`
+    /// limited to the Latin script yields accented Latin letters as well.
+    pub fn filter(mut self, filter: CharFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Deliberately generate difficult characters that the pattern allows
+    ///
+    /// Character classes are narrowed to combining marks, zero-width and
+    /// bidirectional controls, astral-plane characters and homoglyphs of ASCII,
+    /// falling back to any multi-byte character. `DataGenerator::tricks` tells
+    /// which of them the pattern allows, and `adversarial::explain` why a
+    /// value is tricky.
+    pub fn adversarial(mut self, adversarial: bool) -> Self {
+        self.adversarial = adversarial;
+        self
+    }
+
+    pub fn build(self) -> Result<DataGenerator> {
+        // Placeholders become groups checked against their entries for
+        // validation and markers filled after sampling for generation
+        let substitution = dictionary::substitute(&self.pattern, &self.dictionaries)?;
+
+        let mut regex_engine = RegexEngine::new(&substitution.pattern)?;
+        for (group, range) in &self.ranges {
+            regex_engine = regex_engine.with_range(group, range.clone())?;
+        }
+        let entry_sets: Vec<_> = self
+            .dictionaries
+            .iter()
+            .map(|(_, dictionary)| Arc::new(dictionary.entry_set()))
+            .collect();
+        for (group, index) in &substitution.groups {
+            regex_engine = regex_engine.with_entries(group, Arc::clone(&entry_sets[*index]))?;
+        }
+        let expanded = regex_engine.expanded_pattern().to_string();
+
+        let mut tricks = Vec::new();
+        let mut range_markers = Vec::new();
+        let mut dictionary_markers = Vec::new();
+        let rewrites_pattern = !self.ranges.is_empty()
+            || !substitution.groups.is_empty()
+            || self.filter.is_some()
+            || self.adversarial;
+        let regex_generator = if self.ascii_only || rewrites_pattern {
+            // Numeric ranges, dictionaries, character filters and adversarial
+            // generation rewrite the parsed pattern, and ASCII-only generation
+            // parses it with Unicode disabled
+            let mut parser = ParserBuilder::new().unicode(!self.ascii_only).build();
+            let hir = parser
+                .parse(&expanded)
+                .map_err(|e| crate::Error::InvalidRegex(e.to_string()))?;
+            let groups: Vec<&str> = self
+                .ranges
+                .iter()
+                .map(|(group, _)| group.as_str())
+                .chain(substitution.groups.iter().map(|(group, _)| group.as_str()))
+                .collect();
+            let (mut hir, mut markers) = rewrite::substitute_groups(hir, &groups)?;
+            dictionary_markers = markers
+                .split_off(self.ranges.len())
+                .into_iter()
+                .zip(substitution.groups.iter().map(|&(_, index)| index))
+                .collect();
+            range_markers = markers;
+            if let Some(filter) = &self.filter {
+                hir = filter.apply(hir)?;
+            }
+            if self.adversarial {
+                (hir, tricks) = adversarial::bias(hir);
+            }
+            rand_regex::Regex::with_hir(hir, 100)
+        } else {
+            rand_regex::Regex::compile(&expanded, 100)
+        }
+        .map_err(|e| crate::Error::InvalidRegex(e.to_string()))?;
+
+        let rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => {
+                let mut thread_rng = rng();
+                StdRng::from_rng(&mut thread_rng)
+            }
+        };
+
+        Ok(DataGenerator {
             regex_engine,
             regex_generator,
             rng,
-            pattern: pattern.to_string(),
+            pattern: self.pattern,
+            expanded,
+            combined: None,
+            checksum: self.checksum,
+            datetime: self.datetime,
+            ranges: self.ranges,
+            range_markers,
+            dictionaries: self.dictionaries,
+            dictionary_markers,
+            transforms: self.transforms,
+            tricks,
         })
     }
+}
+
+impl DataGenerator {
+    pub fn builder(pattern: &str) -> DataGeneratorBuilder {
+        DataGeneratorBuilder::new(pattern)
+    }
+
+    pub fn new(pattern: &str) -> Result<Self> {
+        Self::builder(pattern).build()
+    }
 
     pub fn with_seed(pattern: &str, seed: u64) -> Result<Self> {
-        let regex_engine = RegexEngine::new(pattern)?;
-        let regex_generator = rand_regex::Regex::compile(pattern, 100)
+        Self::builder(pattern).seed(seed).build()
+    }
+
+    /// Create a generator of dates and times that are not constrained by a pattern
+    pub fn from_datetime(datetime: DateTimeSpec) -> Result<Self> {
+        Self::builder(ANY_PATTERN).datetime(datetime).build()
+    }
+
+    /// Create a generator for strings satisfying several patterns at once
+    ///
+    /// With `CombinationRule::All` values match every pattern; with
+    /// `CombinationRule::FirstExceptRest` they match the first pattern and none of
+    /// the others. Values are drawn by walking the product of the patterns'
+    /// automata, falling back to rejection sampling from the first pattern when
+    /// the product is too large to explore.
+    pub fn with_patterns(patterns: &[&str], rule: CombinationRule) -> Result<Self> {
+        let mut thread_rng = rng();
+        let rng = StdRng::from_rng(&mut thread_rng);
+        Self::from_combination(patterns, rule, rng)
+    }
+
+    /// Create a combined generator with a custom seed
+    pub fn with_patterns_and_seed(
+        patterns: &[&str],
+        rule: CombinationRule,
+        seed: u64,
+    ) -> Result<Self> {
+        Self::from_combination(patterns, rule, StdRng::seed_from_u64(seed))
+    }
+
+    fn from_combination(patterns: &[&str], rule: CombinationRule, rng: StdRng) -> Result<Self> {
+        let combined = CombinedSampler::new(patterns, rule)?;
+        let regex_engine = RegexEngine::new(patterns[0])?;
+        let expanded = regex_engine.expanded_pattern().to_string();
+        let regex_generator = rand_regex::Regex::compile(&expanded, 100)
             .map_err(|e| crate::Error::InvalidRegex(e.to_string()))?;
-        let rng = StdRng::seed_from_u64(seed);
 
         Ok(Self {
             regex_engine,
             regex_generator,
             rng,
-            pattern: pattern.to_string(),
+            pattern: patterns[0].to_string(),
+            expanded,
+            combined: Some(combined),
+            checksum: None,
+            datetime: None,
+            ranges: Vec::new(),
+            range_markers: Vec::new(),
+            dictionaries: Vec::new(),
+            dictionary_markers: Vec::new(),
+            transforms: Vec::new(),
+            tricks: Vec::new(),
         })
     }
 
@@ -52,11 +335,23 @@ impl DataGenerator {
     }
 
     pub fn generate_with_mode(&mut self, count: usize, mode: GenerationMode) -> Result<Vec<String>> {
-        match mode {
-            GenerationMode::Random => self.generate_random(count),
-            GenerationMode::Sequential => self.generate_sequential(count, false),
-            GenerationMode::ReverseSequential => self.generate_sequential(count, true),
-        }
+        let values = match mode {
+            GenerationMode::Random => return self.generate_random(count),
+            GenerationMode::Sequential => self.generate_sequential(count, false)?,
+            GenerationMode::ReverseSequential => self.generate_sequential(count, true)?,
+        };
+
+        // Sequential values cannot be redrawn, so each must take a check digit
+        values
+            .into_iter()
+            .map(|value| {
+                let value = self.apply_transforms(value);
+                match self.apply_checksum(&value) {
+                    Some(value) => Ok(value),
+                    None => Err(self.checksum_error(&value)),
+                }
+            })
+            .collect()
     }
 
     fn generate_random(&mut self, count: usize) -> Result<Vec<String>> {
@@ -71,11 +366,130 @@ impl DataGenerator {
     }
 
     fn generate_single(&mut self) -> Result<String> {
+        if self.checksum.is_none()
+            && self.datetime.is_none()
+            && self.ranges.is_empty()
+            && self.dictionary_markers.is_empty()
+            && self.transforms.is_empty()
+        {
+            return self.sample();
+        }
+
+        for _ in 0..MAX_POST_PROCESS_ATTEMPTS {
+            let value = self.sample()?;
+            let value = self.apply_transforms(value);
+            if let Some(value) = self.apply_checksum(&value) {
+                return Ok(value);
+            }
+        }
+
+        let requirement = match (self.datetime.is_some(), self.checksum) {
+            (_, Some(checksum)) => format!("with a valid {} check digit", checksum.name()),
+            (true, None) => "as a date".to_string(),
+            (false, None) if !self.ranges.is_empty() => "within its numeric ranges".to_string(),
+            (false, None) if !self.transforms.is_empty() => format!(
+                "with the {} transform",
+                self.transforms
+                    .iter()
+                    .map(Transform::name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            (false, None) => "with entries of its dictionaries".to_string(),
+        };
+        Err(crate::Error::GenerationFailed(format!(
+            "No value matching '{}' {} found after {} attempts",
+            self.pattern, requirement, MAX_POST_PROCESS_ATTEMPTS
+        )))
+    }
+
+    fn sample(&mut self) -> Result<String> {
+        if let Some(datetime) = &self.datetime {
+            return datetime.sample(&mut self.rng);
+        }
+
+        if let Some(combined) = &self.combined {
+            return combined.sample(&mut self.rng, &self.regex_generator);
+        }
+
         // Use rand_regex to generate data that actually matches the pattern
-        let generated = self.rng.sample(&self.regex_generator);
+        let mut generated: String = self.rng.sample(&self.regex_generator);
+        if !self.dictionary_markers.is_empty() {
+            generated = dictionary::fill(
+                &generated,
+                &mut self.dictionaries,
+                &self.dictionary_markers,
+                &mut self.rng,
+            )?;
+        }
+        if !self.ranges.is_empty() {
+            generated =
+                numeric::fill_groups(&generated, &self.ranges, &self.range_markers, &mut self.rng);
+        }
         Ok(generated)
     }
 
+    fn apply_transforms(&mut self, value: String) -> String {
+        self.transforms.iter().fold(value, |value, transform| {
+            transform.apply(&value, &mut self.rng)
+        })
+    }
+
+    /// Give `value` a valid check digit while keeping it a match of the pattern
+    fn apply_checksum(&self, value: &str) -> Option<String> {
+        let Some(checksum) = self.checksum else {
+            return self.regex_engine.is_match(value).then(|| value.to_string());
+        };
+        // A partial match would let an appended digit run past the pattern
+        checksum
+            .candidates(value)
+            .into_iter()
+            .find(|candidate| self.regex_engine.is_full_match(candidate))
+    }
+
+    fn checksum_error(&self, value: &str) -> crate::Error {
+        match self.checksum {
+            Some(checksum) => crate::Error::GenerationFailed(format!(
+                "Cannot give '{}' a valid {} check digit matching '{}'",
+                value,
+                checksum.name(),
+                self.pattern
+            )),
+            None if self.ranges.is_empty() => crate::Error::GenerationFailed(format!(
+                "Transformed value '{}' no longer matches '{}'",
+                value, self.pattern
+            )),
+            None => crate::Error::GenerationFailed(format!(
+                "Value '{}' is outside the numeric ranges of '{}'",
+                value, self.pattern
+            )),
+        }
+    }
+
+    /// Checksum applied to generated values, if any
+    pub fn checksum(&self) -> Option<Checksum> {
+        self.checksum
+    }
+
+    /// Difficult characters an adversarial generator can produce
+    ///
+    /// Empty for generators that are not adversarial, and for adversarial
+    /// generators whose pattern allows none of them.
+    pub fn tricks(&self) -> &[Trick] {
+        &self.tricks
+    }
+
+    /// Greatest number of characters in a generated value, if it is known
+    ///
+    /// `None` when the pattern is unbounded, or for dates and numeric ranges,
+    /// whose values are not limited by the shape of the pattern.
+    pub fn max_length(&self) -> Option<usize> {
+        if self.datetime.is_some() || !self.ranges.is_empty() {
+            return None;
+        }
+        self.regex_engine.max_length()
+    }
+
     /// Check if the regex generator can only produce ASCII characters
     pub fn is_ascii(&self) -> bool {
         self.regex_generator.is_ascii()
@@ -88,62 +502,42 @@ impl DataGenerator {
 
     /// Get the original regex pattern
     pub fn pattern(&self) -> &str {
-        self.regex_engine.pattern()
+        &self.pattern
+    }
+
+    /// Get the regex pattern with library references expanded and dictionary
+    /// placeholders replaced by capture groups that validation checks against
+    /// their entries
+    pub fn expanded_pattern(&self) -> &str {
+        &self.expanded
     }
 
     /// Create a new generator with ASCII-only mode for better performance
     pub fn new_ascii_only(pattern: &str) -> Result<Self> {
-        use regex_syntax::ParserBuilder;
-
-        let regex_engine = RegexEngine::new(pattern)?;
-
-        // Parse with Unicode disabled for ASCII-only generation
-        let mut parser = ParserBuilder::new().unicode(false).build();
-        let hir = parser.parse(pattern)
-            .map_err(|e| crate::Error::InvalidRegex(e.to_string()))?;
-
-        let regex_generator = rand_regex::Regex::with_hir(hir, 100)
-            .map_err(|e| crate::Error::InvalidRegex(e.to_string()))?;
-
-        let mut thread_rng = rng();
-        let rng = StdRng::from_rng(&mut thread_rng);
-
-        Ok(Self {
-            regex_engine,
-            regex_generator,
-            rng,
-            pattern: pattern.to_string(),
-        })
+        Self::builder(pattern).ascii_only(true).build()
     }
 
     /// Create a new generator with ASCII-only mode and custom seed
     pub fn with_seed_ascii_only(pattern: &str, seed: u64) -> Result<Self> {
-        use regex_syntax::ParserBuilder;
-
-        let regex_engine = RegexEngine::new(pattern)?;
-
-        // Parse with Unicode disabled for ASCII-only generation
-        let mut parser = ParserBuilder::new().unicode(false).build();
-        let hir = parser.parse(pattern)
-            .map_err(|e| crate::Error::InvalidRegex(e.to_string()))?;
-
-        let regex_generator = rand_regex::Regex::with_hir(hir, 100)
-            .map_err(|e| crate::Error::InvalidRegex(e.to_string()))?;
-
-        let rng = StdRng::seed_from_u64(seed);
-
-        Ok(Self {
-            regex_engine,
-            regex_generator,
-            rng,
-            pattern: pattern.to_string(),
-        })
+        Self::builder(pattern).seed(seed).ascii_only(true).build()
     }
 
     fn generate_sequential(&self, count: usize, reverse: bool) -> Result<Vec<String>> {
+        if self.datetime.is_some() {
+            return Err(crate::Error::GenerationFailed(
+                "Sequential generation not supported for dates".to_string(),
+            ));
+        }
+
+        if self.combined.is_some() {
+            return Err(crate::Error::GenerationFailed(
+                "Sequential generation not supported for combined patterns".to_string(),
+            ));
+        }
+
         // Simple sequential generation for basic patterns
         // This is a simplified implementation focusing on common patterns
-        if let Some(results) = self.generate_simple_sequential(&self.pattern, count, reverse) {
+        if let Some(results) = self.generate_simple_sequential(&self.expanded, count, reverse) {
             Ok(results)
         } else {
             // Fallback to random generation for complex patterns
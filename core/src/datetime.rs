@@ -0,0 +1,269 @@
+use crate::{Error, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{
+    DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, SecondsFormat, Utc,
+};
+use rand::Rng;
+use rand::rngs::StdRng;
+use serde::Deserialize;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// How generated dates and times are written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateTimeFormat {
+    /// ISO 8601 calendar date, as `2024-03-09`
+    Date,
+    /// ISO 8601 time of day, as `14:05:31`
+    Time,
+    /// ISO 8601 date and time with a numeric offset, as `2024-03-09T14:05:31+02:00`
+    Iso8601,
+    /// RFC 3339 timestamp, using `Z` for UTC, as `2024-03-09T12:05:31Z`
+    Rfc3339,
+    /// RFC 2822 timestamp, as `Sat, 9 Mar 2024 14:05:31 +0200`
+    Rfc2822,
+    /// Custom `strftime` format, such as `%d/%m/%Y %H:%M`
+    Strftime(String),
+}
+
+impl FromStr for DateTimeFormat {
+    type Err = Error;
+
+    /// Parse a format name, or a `strftime` format containing `%`
+    fn from_str(format: &str) -> Result<Self> {
+        match format.to_lowercase().as_str() {
+            "date" => Ok(DateTimeFormat::Date),
+            "time" => Ok(DateTimeFormat::Time),
+            "iso8601" | "iso" => Ok(DateTimeFormat::Iso8601),
+            "rfc3339" => Ok(DateTimeFormat::Rfc3339),
+            "rfc2822" => Ok(DateTimeFormat::Rfc2822),
+            _ if format.contains('%') => {
+                let invalid = StrftimeItems::new(format).any(|item| matches!(item, Item::Error));
+                if invalid {
+                    return Err(Error::InvalidDateTime(format!(
+                        "Invalid strftime format '{}'",
+                        format
+                    )));
+                }
+                Ok(DateTimeFormat::Strftime(format.to_string()))
+            }
+            _ => Err(Error::InvalidDateTime(format!(
+                "Unknown format '{}', expected date, time, iso8601, rfc3339, rfc2822 or a strftime format",
+                format
+            ))),
+        }
+    }
+}
+
+/// Real calendar dates and times drawn uniformly from a range
+///
+/// ```no_run
+/// use regex_data_gen_core::{DateTimeFormat, DateTimeSpec};
+///
+/// let spec = DateTimeSpec::new(DateTimeFormat::Rfc3339)
+///     .between("2024-01-01".parse().unwrap(), "2024-12-31".parse().unwrap())
+///     .unwrap()
+///     .with_offset("+09:00".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawDateTimeSpec")]
+pub struct DateTimeSpec {
+    format: DateTimeFormat,
+    start: DateTimeBound,
+    end: DateTimeBound,
+    offset: FixedOffset,
+}
+
+/// Schema representation of a `DateTimeSpec`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawDateTimeSpec {
+    #[serde(default)]
+    format: Option<String>,
+    #[serde(default)]
+    start: Option<String>,
+    #[serde(default)]
+    end: Option<String>,
+    #[serde(default)]
+    offset: Option<String>,
+}
+
+impl TryFrom<RawDateTimeSpec> for DateTimeSpec {
+    type Error = Error;
+
+    fn try_from(raw: RawDateTimeSpec) -> Result<Self> {
+        let format = match raw.format {
+            Some(format) => format.parse()?,
+            None => DateTimeFormat::Rfc3339,
+        };
+        let mut spec = DateTimeSpec::new(format);
+
+        if raw.start.is_some() || raw.end.is_some() {
+            let start = match raw.start {
+                Some(start) => start.parse()?,
+                None => spec.start_bound(),
+            };
+            let end = match raw.end {
+                Some(end) => end.parse()?,
+                None => spec.end_bound(),
+            };
+            spec = spec.between(start, end)?;
+        }
+        if let Some(offset) = raw.offset {
+            spec = spec.with_offset(parse_offset(&offset)?);
+        }
+
+        Ok(spec)
+    }
+}
+
+/// One end of a date range: a full timestamp, or a whole day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateTimeBound {
+    Timestamp(DateTime<FixedOffset>),
+    /// A calendar day in the offset values are written in; as a range end it
+    /// includes the whole day
+    Day(NaiveDate),
+}
+
+impl FromStr for DateTimeBound {
+    type Err = Error;
+
+    /// Parse an RFC 3339 timestamp or an ISO 8601 date
+    fn from_str(value: &str) -> Result<Self> {
+        if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+            return Ok(DateTimeBound::Timestamp(timestamp));
+        }
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(DateTimeBound::Day)
+            .map_err(|_| {
+                Error::InvalidDateTime(format!(
+                    "'{}' is neither an RFC 3339 timestamp nor a YYYY-MM-DD date",
+                    value
+                ))
+            })
+    }
+}
+
+impl DateTimeBound {
+    /// Midnight starting a day at `offset`, or the timestamp itself
+    fn first_instant(&self, offset: FixedOffset) -> DateTime<Utc> {
+        match self {
+            DateTimeBound::Timestamp(timestamp) => timestamp.with_timezone(&Utc),
+            DateTimeBound::Day(day) => local_instant(day.and_time(NaiveTime::MIN), offset),
+        }
+    }
+
+    /// The last millisecond of a day at `offset`, or the timestamp itself
+    fn last_instant(&self, offset: FixedOffset) -> DateTime<Utc> {
+        match self {
+            DateTimeBound::Timestamp(timestamp) => timestamp.with_timezone(&Utc),
+            DateTimeBound::Day(day) => match day.and_hms_milli_opt(23, 59, 59, 999) {
+                Some(last) => local_instant(last, offset),
+                None => self.first_instant(offset),
+            },
+        }
+    }
+}
+
+fn local_instant(local: NaiveDateTime, offset: FixedOffset) -> DateTime<Utc> {
+    local
+        .and_local_timezone(offset)
+        .single()
+        .map(|instant| instant.with_timezone(&Utc))
+        // Only dates at the very ends of chrono's range cannot be shifted
+        .unwrap_or_else(|| local.and_utc())
+}
+
+impl DateTimeSpec {
+    /// Dates between 2000-01-01 and 2030-12-31, written in UTC
+    pub fn new(format: DateTimeFormat) -> Self {
+        Self {
+            format,
+            start: DateTimeBound::Day(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default()),
+            end: DateTimeBound::Day(NaiveDate::from_ymd_opt(2030, 12, 31).unwrap_or_default()),
+            offset: Utc.fix(),
+        }
+    }
+
+    /// Restrict values to the inclusive range from `start` to `end`
+    ///
+    /// Days run from midnight to midnight at the offset set by `with_offset`,
+    /// whether it is set before or after the range.
+    pub fn between(mut self, start: DateTimeBound, end: DateTimeBound) -> Result<Self> {
+        self.start = start;
+        self.end = end;
+        self.instants()?;
+        Ok(self)
+    }
+
+    /// Write values in the time zone with the given UTC offset
+    pub fn with_offset(mut self, offset: FixedOffset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// First and last instants of the range at the output offset
+    fn instants(&self) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+        let start = self.start.first_instant(self.offset);
+        let end = self.end.last_instant(self.offset);
+        if start > end {
+            return Err(Error::InvalidDateTime(format!(
+                "Range start {} is after its end {}",
+                start.to_rfc3339(),
+                end.to_rfc3339()
+            )));
+        }
+        Ok((start, end))
+    }
+
+    pub fn format(&self) -> &DateTimeFormat {
+        &self.format
+    }
+
+    /// Earliest value of the range
+    pub fn start_bound(&self) -> DateTimeBound {
+        self.start
+    }
+
+    /// Latest value of the range
+    pub fn end_bound(&self) -> DateTimeBound {
+        self.end
+    }
+
+    pub(crate) fn sample(&self, rng: &mut StdRng) -> Result<String> {
+        // A date and a timestamp may only be out of order at some offsets
+        let (start, end) = self.instants()?;
+        let seconds = rng.random_range(start.timestamp()..=end.timestamp());
+        let instant = DateTime::from_timestamp(seconds, 0)
+            .ok_or_else(|| Error::GenerationFailed(format!("Timestamp {} out of range", seconds)))?
+            .with_timezone(&self.offset);
+
+        let formatted = match &self.format {
+            DateTimeFormat::Date => instant.format("%Y-%m-%d").to_string(),
+            DateTimeFormat::Time => instant.format("%H:%M:%S").to_string(),
+            DateTimeFormat::Iso8601 => instant.format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+            DateTimeFormat::Rfc3339 => instant.to_rfc3339_opts(SecondsFormat::Secs, true),
+            DateTimeFormat::Rfc2822 => instant.to_rfc2822(),
+            DateTimeFormat::Strftime(format) => {
+                let mut formatted = String::new();
+                write!(formatted, "{}", instant.format(format)).map_err(|_| {
+                    Error::InvalidDateTime(format!("Cannot format a date with '{}'", format))
+                })?;
+                formatted
+            }
+        };
+
+        Ok(formatted)
+    }
+}
+
+/// Parse a UTC offset such as `Z`, `+02:00` or `-0530`
+pub fn parse_offset(offset: &str) -> Result<FixedOffset> {
+    if offset.eq_ignore_ascii_case("z") || offset.eq_ignore_ascii_case("utc") {
+        return Ok(Utc.fix());
+    }
+    offset
+        .parse::<FixedOffset>()
+        .map_err(|_| Error::InvalidDateTime(format!("Invalid UTC offset '{}'", offset)))
+}
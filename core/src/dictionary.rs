@@ -0,0 +1,276 @@
+use crate::importers::{CsvImporter, Importer};
+use crate::{Error, Result};
+use rand::Rng;
+use rand::rngs::StdRng;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+/// Prefix of the capture groups standing in for placeholders when validating
+const GROUP_PREFIX: &str = "__dict";
+
+/// A word list drawn from by `{{dict:name}}` placeholders in a pattern
+///
+/// Entries are drawn uniformly unless weights are given, using the
+/// generator's random number generator so seeded runs repeat. A unique
+/// dictionary never repeats an entry and fails once every entry was used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dictionary {
+    entries: Vec<String>,
+    weights: Vec<f64>,
+    unique: bool,
+    /// Indices of entries that can still be drawn
+    remaining: Vec<usize>,
+}
+
+/// Where a dictionary is loaded from, as written in schema files
+///
+/// ```toml
+/// [fields.dictionaries.cities]
+/// path = "cities.csv"
+/// column = "name"
+/// weight_column = "population"
+/// unique = true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DictionarySource {
+    pub path: PathBuf,
+    /// CSV column holding the entries; without it the file is a plain word list
+    #[serde(default)]
+    pub column: Option<String>,
+    /// CSV column holding the weight of each entry
+    #[serde(default)]
+    pub weight_column: Option<String>,
+    #[serde(default)]
+    pub unique: bool,
+}
+
+impl DictionarySource {
+    pub fn load(&self) -> Result<Dictionary> {
+        let path = self.path.to_string_lossy();
+        let dictionary = match (&self.column, &self.weight_column) {
+            (Some(column), weight_column) => {
+                Dictionary::from_csv(&path, column, weight_column.as_deref())?
+            }
+            (None, None) => Dictionary::from_file(&path)?,
+            (None, Some(_)) => {
+                return Err(Error::ImportFailed(format!(
+                    "Dictionary '{}' has a weight column but no column",
+                    path
+                )));
+            }
+        };
+
+        Ok(if self.unique {
+            dictionary.unique()
+        } else {
+            dictionary
+        })
+    }
+}
+
+impl Dictionary {
+    pub fn new<I, S>(entries: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let entries: Vec<String> = entries.into_iter().map(Into::into).collect();
+        if entries.is_empty() {
+            return Err(Error::ImportFailed("Dictionary has no entries".to_string()));
+        }
+
+        Ok(Self {
+            weights: vec![1.0; entries.len()],
+            remaining: (0..entries.len()).collect(),
+            entries,
+            unique: false,
+        })
+    }
+
+    /// Load a word list with one entry per line
+    ///
+    /// Blank lines are skipped, and a line of `entry<TAB>weight` gives the
+    /// entry a weight.
+    pub fn from_file(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            Error::ImportFailed(format!("Failed to read dictionary '{}': {}", path, e))
+        })?;
+
+        let mut entries = Vec::new();
+        let mut weights = Vec::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let (entry, weight) = match line.rsplit_once('\t') {
+                Some((entry, weight)) => (entry, parse_weight(weight)?),
+                None => (line, 1.0),
+            };
+            entries.push(entry.to_string());
+            weights.push(weight);
+        }
+
+        Self::new(entries)?.with_weights(weights)
+    }
+
+    /// Load the entries of a CSV column, optionally weighted by another column
+    pub fn from_csv(path: &str, column: &str, weight_column: Option<&str>) -> Result<Self> {
+        let entries: Vec<String> = CsvImporter::with_column(column.to_string())
+            .import(path)?
+            .into_iter()
+            .map(|imported| imported.value)
+            .collect();
+        let dictionary = Self::new(entries)?;
+
+        match weight_column {
+            Some(weight_column) => {
+                let weights = CsvImporter::with_column(weight_column.to_string())
+                    .import(path)?
+                    .iter()
+                    .map(|imported| parse_weight(&imported.value))
+                    .collect::<Result<Vec<_>>>()?;
+                dictionary.with_weights(weights)
+            }
+            None => Ok(dictionary),
+        }
+    }
+
+    /// Draw entries in proportion to `weights`, one per entry
+    pub fn with_weights(mut self, weights: Vec<f64>) -> Result<Self> {
+        if weights.len() != self.entries.len() {
+            return Err(Error::ImportFailed(format!(
+                "Dictionary has {} entries but {} weights",
+                self.entries.len(),
+                weights.len()
+            )));
+        }
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) || weights.iter().sum::<f64>() <= 0.0
+        {
+            return Err(Error::ImportFailed(
+                "Dictionary weights must be non-negative and not all zero".to_string(),
+            ));
+        }
+
+        self.weights = weights;
+        Ok(self)
+    }
+
+    /// Never draw the same entry twice
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    pub(crate) fn draw(&mut self, rng: &mut StdRng) -> Option<&str> {
+        let total: f64 = self.remaining.iter().map(|&i| self.weights[i]).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut target = rng.random_range(0.0..total);
+        let mut position = self.remaining.len() - 1;
+        for (p, &i) in self.remaining.iter().enumerate() {
+            if target < self.weights[i] {
+                position = p;
+                break;
+            }
+            target -= self.weights[i];
+        }
+
+        let index = if self.unique {
+            self.remaining.swap_remove(position)
+        } else {
+            self.remaining[position]
+        };
+        Some(&self.entries[index])
+    }
+
+    /// Every entry, used to validate values
+    pub(crate) fn entry_set(&self) -> HashSet<String> {
+        self.entries.iter().cloned().collect()
+    }
+}
+
+fn parse_weight(weight: &str) -> Result<f64> {
+    weight
+        .trim()
+        .parse()
+        .map_err(|_| Error::ImportFailed(format!("Invalid dictionary weight '{}'", weight)))
+}
+
+/// A pattern with its `{{dict:name}}` placeholders replaced
+pub(crate) struct Substitution {
+    /// Pattern where each placeholder is a capture group taking any text
+    pub pattern: String,
+    /// Name of each placeholder's group and index of its dictionary, whose
+    /// entries the group must hold
+    pub groups: Vec<(String, usize)>,
+}
+
+/// Replace `{{dict:name}}` placeholders of `pattern` with capture groups
+///
+/// Generation replaces the groups with markers, see `rewrite::substitute_groups`,
+/// and validation checks that they hold entries of their dictionaries.
+pub(crate) fn substitute(
+    pattern: &str,
+    dictionaries: &[(String, Dictionary)],
+) -> Result<Substitution> {
+    let mut substituted = String::with_capacity(pattern.len());
+    let mut groups = Vec::new();
+    let mut rest = pattern;
+
+    while let Some(start) = rest.find("{{dict:") {
+        let after = &rest[start + "{{dict:".len()..];
+        let end = after.find("}}").ok_or_else(|| {
+            Error::InvalidRegex(format!("Unclosed dictionary placeholder in '{}'", pattern))
+        })?;
+        let name = after[..end].trim();
+        let index = dictionaries
+            .iter()
+            .position(|(n, _)| n == name)
+            .ok_or_else(|| Error::InvalidRegex(format!("Unknown dictionary '{}'", name)))?;
+        let group = format!("{}{}", GROUP_PREFIX, groups.len());
+
+        // Unicode stays on so that the group parses for ASCII-only generation
+        substituted.push_str(&rest[..start]);
+        substituted.push_str(&format!("(?P<{}>(?su:.*?))", group));
+        groups.push((group, index));
+        rest = &after[end + 2..];
+    }
+    substituted.push_str(rest);
+
+    Ok(Substitution {
+        pattern: substituted,
+        groups,
+    })
+}
+
+/// Replace markers with entries drawn from the dictionary at their index
+pub(crate) fn fill(
+    value: &str,
+    dictionaries: &mut [(String, Dictionary)],
+    markers: &[(char, usize)],
+    rng: &mut StdRng,
+) -> Result<String> {
+    let mut filled = String::with_capacity(value.len());
+    for c in value.chars() {
+        match markers.iter().find(|(marker, _)| *marker == c) {
+            Some(&(_, index)) => {
+                let (name, dictionary) = &mut dictionaries[index];
+                let entry = dictionary.draw(rng).ok_or_else(|| {
+                    Error::GenerationFailed(format!(
+                        "Dictionary '{}' has no unused entries left",
+                        name
+                    ))
+                })?;
+                filled.push_str(entry);
+            }
+            None => filled.push(c),
+        }
+    }
+    Ok(filled)
+}
@@ -0,0 +1,245 @@
+use super::Exporter;
+use crate::{Error, RecordSet, Result};
+use avro_schema::file::{Block, CompressedBlock, Compression};
+use avro_schema::schema::{Field, Record, Schema};
+use avro_schema::write::encode::zigzag_encode;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Uncompressed bytes per data block before it is written
+const BLOCK_SIZE: usize = 64 * 1024;
+
+/// Codec compressing the data blocks of an Avro container file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AvroCompression {
+    /// The `null` codec
+    #[default]
+    None,
+    Deflate,
+    Snappy,
+}
+
+impl AvroCompression {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AvroCompression::None => "null",
+            AvroCompression::Deflate => "deflate",
+            AvroCompression::Snappy => "snappy",
+        }
+    }
+
+    fn codec(&self) -> Option<Compression> {
+        match self {
+            AvroCompression::None => None,
+            AvroCompression::Deflate => Some(Compression::Deflate),
+            AvroCompression::Snappy => Some(Compression::Snappy),
+        }
+    }
+}
+
+/// Writes Avro object container files with an embedded record schema
+///
+/// Every header becomes a `string` field of the record, so names must be
+/// valid Avro names: a letter or underscore followed by letters, digits or
+/// underscores.
+///
+/// ```no_run
+/// use regex_data_gen_core::{AvroCompression, AvroExporter, Exporter};
+///
+/// let exporter = AvroExporter::with_headers(vec!["email".to_string()])
+///     .with_record_name("User")
+///     .with_compression(AvroCompression::Deflate);
+/// exporter.export(&["a@example.com".to_string()], "users.avro").unwrap();
+/// ```
+pub struct AvroExporter {
+    headers: Vec<String>,
+    record_name: String,
+    compression: AvroCompression,
+}
+
+impl AvroExporter {
+    pub fn new() -> Self {
+        Self::with_headers(vec!["generated_data".to_string()])
+    }
+
+    pub fn with_headers(headers: Vec<String>) -> Self {
+        Self {
+            headers,
+            record_name: "GeneratedData".to_string(),
+            compression: AvroCompression::default(),
+        }
+    }
+
+    /// Name of the record type in the schema
+    pub fn with_record_name(mut self, record_name: &str) -> Self {
+        self.record_name = record_name.to_string();
+        self
+    }
+
+    pub fn with_compression(mut self, compression: AvroCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Avro schema of the records written for `columns`, as JSON
+    pub fn schema(&self, columns: &[String]) -> Result<String> {
+        serde_json::to_string(&Schema::Record(self.record(columns)?))
+            .map_err(|e| Error::ExportFailed(format!("Failed to serialize Avro schema: {}", e)))
+    }
+
+    /// Start streaming records with a field per header to `writer`
+    pub fn writer<W: Write>(&self, writer: W) -> Result<AvroWriter<W>> {
+        AvroWriter::new(writer, self.record(&self.headers)?, self.compression)
+    }
+
+    fn record(&self, columns: &[String]) -> Result<Record> {
+        for name in columns.iter().chain([&self.record_name]) {
+            if !is_avro_name(name) {
+                return Err(Error::ExportFailed(format!(
+                    "'{}' is not a valid Avro name",
+                    name
+                )));
+            }
+        }
+        let fields = columns
+            .iter()
+            .map(|column| Field::new(column, Schema::String(None)))
+            .collect();
+        Ok(Record::new(&self.record_name, fields))
+    }
+
+    fn create(&self, output_path: &str, columns: &[String]) -> Result<AvroWriter<BufWriter<File>>> {
+        let record = self.record(columns)?;
+        let file = File::create(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to create Avro file: {}", e)))?;
+        AvroWriter::new(BufWriter::new(file), record, self.compression)
+    }
+}
+
+impl Default for AvroExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Streams records to a writer, one Avro data block at a time
+pub struct AvroWriter<W: Write> {
+    writer: W,
+    fields: usize,
+    compression: Option<Compression>,
+    /// Encoded records of the block being filled
+    block: Block,
+    compressed: CompressedBlock,
+}
+
+impl<W: Write> AvroWriter<W> {
+    fn new(mut writer: W, record: Record, compression: AvroCompression) -> Result<Self> {
+        let fields = record.fields.len();
+        let mut header = Vec::new();
+        avro_schema::write::write_metadata(&mut header, record, compression.codec())
+            .map_err(avro_error)?;
+        writer.write_all(&header).map_err(write_error)?;
+        Ok(Self {
+            writer,
+            fields,
+            compression: compression.codec(),
+            block: Block::default(),
+            compressed: CompressedBlock::default(),
+        })
+    }
+
+    /// Write a value to a single-field record
+    pub fn write(&mut self, value: &str) -> Result<()> {
+        self.write_record(&[value])
+    }
+
+    /// Write a record holding a value per field, in field order
+    pub fn write_record<S: AsRef<str>>(&mut self, row: &[S]) -> Result<()> {
+        if row.len() != self.fields {
+            return Err(Error::ExportFailed(format!(
+                "Expected {} Avro fields, got {}",
+                self.fields,
+                row.len()
+            )));
+        }
+        for value in row {
+            let value = value.as_ref().as_bytes();
+            zigzag_encode(value.len() as i64, &mut self.block.data).map_err(avro_error)?;
+            self.block.data.extend_from_slice(value);
+        }
+        self.block.number_of_rows += 1;
+        if self.block.data.len() >= BLOCK_SIZE {
+            self.write_block()?;
+        }
+        Ok(())
+    }
+
+    /// Write the last block and give back the writer
+    pub fn finish(mut self) -> Result<W> {
+        self.write_block()?;
+        self.writer.flush().map_err(write_error)?;
+        Ok(self.writer)
+    }
+
+    fn write_block(&mut self) -> Result<()> {
+        if self.block.number_of_rows == 0 {
+            return Ok(());
+        }
+        avro_schema::write::compress(&mut self.block, &mut self.compressed, self.compression)
+            .map_err(avro_error)?;
+        let mut bytes = Vec::with_capacity(self.compressed.data.len() + 32);
+        avro_schema::write::write_block(&mut bytes, &self.compressed).map_err(avro_error)?;
+        self.writer.write_all(&bytes).map_err(write_error)?;
+
+        self.block.number_of_rows = 0;
+        self.block.data.clear();
+        Ok(())
+    }
+}
+
+fn avro_error(e: avro_schema::error::Error) -> Error {
+    Error::ExportFailed(format!("Failed to encode Avro data: {}", e))
+}
+
+fn write_error(e: std::io::Error) -> Error {
+    Error::ExportFailed(format!("Failed to write Avro file: {}", e))
+}
+
+/// Whether `name` matches `[A-Za-z_][A-Za-z0-9_]*`
+fn is_avro_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl Exporter for AvroExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        if self.headers.len() != 1 {
+            return Err(Error::ExportFailed(format!(
+                "Values need exactly one Avro field, got {} headers",
+                self.headers.len()
+            )));
+        }
+        let mut writer = self.create(output_path, &self.headers)?;
+        for value in data {
+            writer.write(value)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        let mut writer = self.create(output_path, records.columns())?;
+        for row in records.rows() {
+            writer.write_record(row)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn format_name(&self) -> &'static str {
+        "Avro"
+    }
+}
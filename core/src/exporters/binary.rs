@@ -0,0 +1,176 @@
+use super::Exporter;
+use super::json::{records_document, values_document};
+use crate::{Error, RecordSet, Result};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Writes MessagePack with the same layout as `JsonExporter`
+///
+/// Values become an array of strings, or of `{"id": ..., "value": ...}` maps
+/// when `array_format` is unset; records become an array of maps keyed by
+/// column name.
+#[cfg(feature = "msgpack")]
+pub struct MessagePackExporter {
+    array_format: bool,
+}
+
+#[cfg(feature = "msgpack")]
+impl MessagePackExporter {
+    pub fn new() -> Self {
+        Self { array_format: true }
+    }
+
+    pub fn with_options(array_format: bool) -> Self {
+        Self { array_format }
+    }
+
+    fn write_document(&self, document: &Value, output_path: &str) -> Result<()> {
+        let mut writer = create(output_path, "MessagePack")?;
+        rmp_serde::encode::write(&mut writer, document)
+            .map_err(|e| Error::ExportFailed(format!("Failed to serialize MessagePack: {}", e)))?;
+        flush(writer, "MessagePack")
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl Default for MessagePackExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl Exporter for MessagePackExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        self.write_document(&values_document(data, self.array_format), output_path)
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        self.write_document(&records_document(records), output_path)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "MessagePack"
+    }
+}
+
+/// Writes CBOR with the same layout as `JsonExporter`
+#[cfg(feature = "cbor")]
+pub struct CborExporter {
+    array_format: bool,
+}
+
+#[cfg(feature = "cbor")]
+impl CborExporter {
+    pub fn new() -> Self {
+        Self { array_format: true }
+    }
+
+    pub fn with_options(array_format: bool) -> Self {
+        Self { array_format }
+    }
+
+    fn write_document(&self, document: &Value, output_path: &str) -> Result<()> {
+        let mut writer = create(output_path, "CBOR")?;
+        ciborium::into_writer(document, &mut writer)
+            .map_err(|e| Error::ExportFailed(format!("Failed to serialize CBOR: {}", e)))?;
+        flush(writer, "CBOR")
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl Default for CborExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl Exporter for CborExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        self.write_document(&values_document(data, self.array_format), output_path)
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        self.write_document(&records_document(records), output_path)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "CBOR"
+    }
+}
+
+/// Writes BSON documents back to back, as in a `mongodump` `.bson` file
+///
+/// BSON has no top-level arrays, so each element of the `JsonExporter`
+/// layout is its own document. Plain strings, which cannot be documents, are
+/// written as `{"value": ...}`.
+#[cfg(feature = "bson")]
+pub struct BsonExporter {
+    array_format: bool,
+}
+
+#[cfg(feature = "bson")]
+impl BsonExporter {
+    pub fn new() -> Self {
+        Self { array_format: true }
+    }
+
+    pub fn with_options(array_format: bool) -> Self {
+        Self { array_format }
+    }
+
+    fn write_documents(&self, document: Value, output_path: &str) -> Result<()> {
+        let mut writer = create(output_path, "BSON")?;
+        let Value::Array(elements) = document else {
+            unreachable!("exported documents are arrays");
+        };
+        for element in elements {
+            let element = match element {
+                Value::String(value) => serde_json::json!({ "value": value }),
+                element => element,
+            };
+            let document = bson::to_document(&element)
+                .map_err(|e| Error::ExportFailed(format!("Failed to serialize BSON: {}", e)))?;
+            document
+                .to_writer(&mut writer)
+                .map_err(|e| Error::ExportFailed(format!("Failed to write BSON file: {}", e)))?;
+        }
+        flush(writer, "BSON")
+    }
+}
+
+#[cfg(feature = "bson")]
+impl Default for BsonExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "bson")]
+impl Exporter for BsonExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        self.write_documents(values_document(data, self.array_format), output_path)
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        self.write_documents(records_document(records), output_path)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "BSON"
+    }
+}
+
+fn create(output_path: &str, format: &str) -> Result<BufWriter<File>> {
+    let file = File::create(output_path)
+        .map_err(|e| Error::ExportFailed(format!("Failed to create {} file: {}", format, e)))?;
+    Ok(BufWriter::new(file))
+}
+
+fn flush(mut writer: BufWriter<File>, format: &str) -> Result<()> {
+    writer
+        .flush()
+        .map_err(|e| Error::ExportFailed(format!("Failed to write {} file: {}", format, e)))
+}
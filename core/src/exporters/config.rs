@@ -0,0 +1,326 @@
+use super::Exporter;
+use super::json::{records_document, values_document};
+use crate::{Error, RecordSet, Result};
+use serde_json::{Map, Value};
+use std::fs;
+
+/// Shape of the document written by `YamlExporter` and `TomlExporter`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DocumentLayout {
+    /// A list of values, or of records as maps
+    #[default]
+    List,
+    /// A list of `{id, value}` maps, or of records as maps
+    Objects,
+    /// A map from each value to its index, or from the first column of each
+    /// record to a map of the other columns
+    ///
+    /// Keys must be unique, so duplicate values fail the export.
+    Map,
+}
+
+impl DocumentLayout {
+    fn values(&self, data: &[String]) -> Result<Value> {
+        match self {
+            DocumentLayout::List => Ok(values_document(data, true)),
+            DocumentLayout::Objects => Ok(values_document(data, false)),
+            DocumentLayout::Map => keyed(
+                data.iter()
+                    .enumerate()
+                    .map(|(i, value)| (value.clone(), Value::from(i))),
+            ),
+        }
+    }
+
+    fn records(&self, records: &RecordSet) -> Result<Value> {
+        match self {
+            DocumentLayout::List | DocumentLayout::Objects => Ok(records_document(records)),
+            DocumentLayout::Map => {
+                if records.columns().is_empty() {
+                    return Err(Error::ExportFailed(
+                        "Map layout needs at least one column".to_string(),
+                    ));
+                }
+                let columns = &records.columns()[1..];
+                keyed(records.rows().iter().map(|row| {
+                    let fields: Map<String, Value> = columns
+                        .iter()
+                        .zip(&row[1..])
+                        .map(|(column, value)| (column.clone(), Value::String(value.clone())))
+                        .collect();
+                    (row[0].clone(), Value::Object(fields))
+                }))
+            }
+        }
+    }
+}
+
+fn keyed(entries: impl Iterator<Item = (String, Value)>) -> Result<Value> {
+    let mut map = Map::new();
+    for (key, value) in entries {
+        if map.contains_key(&key) {
+            return Err(Error::ExportFailed(format!(
+                "Duplicate key '{}' in map layout",
+                key
+            )));
+        }
+        map.insert(key, value);
+    }
+    Ok(Value::Object(map))
+}
+
+/// Writes YAML documents, quoting strings YAML would read as another type
+///
+/// Strings that YAML 1.1 or 1.2 parsers could read as booleans, nulls,
+/// numbers or dates, such as `yes`, `off`, `null`, `~`, `0123` or
+/// `2024-01-01`, are single-quoted, as are strings starting with an indicator
+/// character. Multiline strings are written as literal blocks, and strings
+/// with control characters are double-quoted with escapes.
+pub struct YamlExporter {
+    layout: DocumentLayout,
+}
+
+impl YamlExporter {
+    pub fn new() -> Self {
+        Self {
+            layout: DocumentLayout::default(),
+        }
+    }
+
+    pub fn with_layout(layout: DocumentLayout) -> Self {
+        Self { layout }
+    }
+
+    fn write_document(&self, document: &Value, output_path: &str) -> Result<()> {
+        let mut yaml = String::new();
+        write_yaml(&mut yaml, document, 0);
+        fs::write(output_path, yaml)
+            .map_err(|e| Error::ExportFailed(format!("Failed to write YAML file: {}", e)))
+    }
+}
+
+/// Append `value` as a block node indented by `indent` spaces
+///
+/// Documents only hold strings, numbers, and lists and maps of them.
+fn write_yaml(out: &mut String, value: &Value, indent: usize) {
+    let padding = " ".repeat(indent);
+    match value {
+        Value::Array(items) if items.is_empty() => out.push_str("[]\n"),
+        Value::Object(map) if map.is_empty() => out.push_str("{}\n"),
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(&padding);
+                }
+                out.push_str("- ");
+                write_yaml(out, item, indent + 2);
+            }
+        }
+        Value::Object(map) => {
+            for (i, (key, item)) in map.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(&padding);
+                }
+                let key = yaml_key(key);
+                if key.len() > 1024 {
+                    // Implicit keys are limited to 1024 characters
+                    out.push_str(&format!("? {}\n{}:", key, padding));
+                } else {
+                    out.push_str(&key);
+                    out.push(':');
+                }
+                match item {
+                    Value::Object(fields) if !fields.is_empty() => {
+                        out.push('\n');
+                        out.push_str(&" ".repeat(indent + 2));
+                        write_yaml(out, item, indent + 2);
+                    }
+                    _ => {
+                        out.push(' ');
+                        write_yaml(out, item, indent + 2);
+                    }
+                }
+            }
+        }
+        Value::String(text) => {
+            out.push_str(&yaml_string(text, indent));
+            out.push('\n');
+        }
+        other => {
+            out.push_str(&other.to_string());
+            out.push('\n');
+        }
+    }
+}
+
+fn yaml_key(key: &str) -> String {
+    if key.contains(['\n', '\r']) {
+        double_quoted(key)
+    } else {
+        yaml_string(key, 0)
+    }
+}
+
+/// A string scalar, plain when no parser could misread it
+fn yaml_string(text: &str, indent: usize) -> String {
+    if text.chars().any(needs_escape) {
+        double_quoted(text)
+    } else if text.contains('\n') {
+        // Blocks take leading spaces for indentation and cannot hold only
+        // newlines, so such strings are escaped instead
+        if text.trim_matches('\n').is_empty() || text.trim_start_matches('\n').starts_with(' ') {
+            double_quoted(text)
+        } else {
+            literal_block(text, indent)
+        }
+    } else if is_plain_safe(text) {
+        text.to_string()
+    } else {
+        format!("'{}'", text.replace('\'', "''"))
+    }
+}
+
+/// Characters a YAML file cannot hold outside double-quoted escapes
+fn needs_escape(c: char) -> bool {
+    (c.is_control() && c != '\n')
+        || matches!(c, '\u{FEFF}' | '\u{2028}' | '\u{2029}')
+        || matches!(c, '\u{FFFE}' | '\u{FFFF}')
+}
+
+fn is_plain_safe(text: &str) -> bool {
+    const SPECIAL: &[&str] = &[
+        "y", "yes", "n", "no", "true", "false", "on", "off", "null", "~", "<<", "=",
+    ];
+    let Some(first) = text.chars().next() else {
+        return false;
+    };
+    !first.is_whitespace()
+        && !text.ends_with(char::is_whitespace)
+        // Indicators, and the starts of numbers, dates and times
+        && !"-?:,[]{}#&*!|>'\"%@`+.".contains(first)
+        && !first.is_ascii_digit()
+        && !text.contains(": ")
+        && !text.contains(" #")
+        && !text.ends_with(':')
+        && !SPECIAL.contains(&text.to_ascii_lowercase().as_str())
+}
+
+/// A `|` block; chomping keeps exactly the trailing newlines of `text`
+fn literal_block(text: &str, indent: usize) -> String {
+    let content = text.trim_end_matches('\n');
+    let chomping = match text.len() - content.len() {
+        0 => "-",
+        1 => "",
+        _ => "+",
+    };
+    let padding = " ".repeat(indent);
+    let mut block = format!("|{}", chomping);
+    for line in text.strip_suffix('\n').unwrap_or(text).split('\n') {
+        block.push('\n');
+        if !line.is_empty() {
+            block.push_str(&padding);
+            block.push_str(line);
+        }
+    }
+    block
+}
+
+fn double_quoted(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\0' => quoted.push_str("\\0"),
+            c if needs_escape(c) && (c as u32) <= 0xFF => {
+                quoted.push_str(&format!("\\x{:02X}", c as u32))
+            }
+            c if needs_escape(c) => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Default for YamlExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter for YamlExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        self.write_document(&self.layout.values(data)?, output_path)
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        self.write_document(&self.layout.records(records)?, output_path)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "YAML"
+    }
+}
+
+/// Writes TOML documents
+///
+/// A TOML document is a table, so lists are stored under a key, `data` by
+/// default, while the map layout forms the top-level table itself.
+pub struct TomlExporter {
+    layout: DocumentLayout,
+    key: String,
+}
+
+impl TomlExporter {
+    pub fn new() -> Self {
+        Self::with_layout(DocumentLayout::default())
+    }
+
+    pub fn with_layout(layout: DocumentLayout) -> Self {
+        Self {
+            layout,
+            key: "data".to_string(),
+        }
+    }
+
+    /// Key holding the list layouts
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.key = key.to_string();
+        self
+    }
+
+    fn write_document(&self, document: Value, output_path: &str) -> Result<()> {
+        let table = match document {
+            Value::Object(table) => table,
+            list => Map::from_iter([(self.key.clone(), list)]),
+        };
+        let toml = toml::to_string(&table)
+            .map_err(|e| Error::ExportFailed(format!("Failed to serialize TOML: {}", e)))?;
+        fs::write(output_path, toml)
+            .map_err(|e| Error::ExportFailed(format!("Failed to write TOML file: {}", e)))
+    }
+}
+
+impl Default for TomlExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter for TomlExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        self.write_document(self.layout.values(data)?, output_path)
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        self.write_document(self.layout.records(records)?, output_path)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "TOML"
+    }
+}
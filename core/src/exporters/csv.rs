@@ -1,5 +1,5 @@
 use super::Exporter;
-use crate::{Error, Result};
+use crate::{Error, RecordSet, Result};
 use csv::Writer;
 use std::fs::File;
 
@@ -51,6 +51,29 @@ impl Exporter for CsvExporter {
         Ok(())
     }
 
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        let file = File::create(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to create CSV file: {}", e)))?;
+
+        let mut writer = Writer::from_writer(file);
+
+        writer
+            .write_record(records.columns())
+            .map_err(|e| Error::ExportFailed(format!("Failed to write CSV headers: {}", e)))?;
+
+        for row in records.rows() {
+            writer
+                .write_record(row)
+                .map_err(|e| Error::ExportFailed(format!("Failed to write CSV record: {}", e)))?;
+        }
+
+        writer
+            .flush()
+            .map_err(|e| Error::ExportFailed(format!("Failed to flush CSV writer: {}", e)))?;
+
+        Ok(())
+    }
+
     fn format_name(&self) -> &'static str {
         "CSV"
     }
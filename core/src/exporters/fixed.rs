@@ -0,0 +1,205 @@
+use super::Exporter;
+use crate::{Error, RecordSet, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Side of a fixed-width field that values are aligned to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alignment {
+    /// Pad on the right, as COBOL does for alphanumeric fields
+    #[default]
+    Left,
+    /// Pad on the left, as COBOL does for numeric fields
+    Right,
+}
+
+/// Writes fixed-width records, one line per value or record with no header
+///
+/// Each field is padded to the width of its column. Columns without a
+/// declared width are as wide as their longest value; the CLI declares
+/// `DataGenerator::max_length` so that widths do not depend on the values
+/// that happened to be generated.
+///
+/// Values longer than their column fail the export unless truncation is
+/// enabled. Truncation keeps the aligned end of the value, so left-aligned
+/// values lose their last characters and right-aligned values their first,
+/// as with a COBOL `MOVE`. Widths count characters, not bytes.
+///
+/// ```no_run
+/// use regex_data_gen_core::{Alignment, Exporter, FixedWidthExporter};
+///
+/// let exporter = FixedWidthExporter::new()
+///     .with_width("generated_data", 10)
+///     .with_alignment(Alignment::Right)
+///     .with_pad_char('0');
+/// exporter.export(&["4217".to_string()], "amounts.dat").unwrap();
+/// ```
+pub struct FixedWidthExporter {
+    column: String,
+    widths: Vec<(String, usize)>,
+    default_width: Option<usize>,
+    alignment: Alignment,
+    pad_char: char,
+    truncate: bool,
+}
+
+impl FixedWidthExporter {
+    pub fn new() -> Self {
+        Self {
+            column: "generated_data".to_string(),
+            widths: Vec::new(),
+            default_width: None,
+            alignment: Alignment::default(),
+            pad_char: ' ',
+            truncate: false,
+        }
+    }
+
+    /// Column holding plain values; records use their own column names
+    pub fn with_column(mut self, column: &str) -> Self {
+        self.column = column.to_string();
+        self
+    }
+
+    /// Declare the width of `column`, in characters
+    pub fn with_width(mut self, column: &str, width: usize) -> Self {
+        self.widths.retain(|(name, _)| name != column);
+        self.widths.push((column.to_string(), width));
+        self
+    }
+
+    /// Width of columns without a declared width, instead of their longest
+    /// value
+    pub fn with_default_width(mut self, width: usize) -> Self {
+        self.default_width = Some(width);
+        self
+    }
+
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Character filling the rest of each field, a space by default
+    pub fn with_pad_char(mut self, pad_char: char) -> Self {
+        self.pad_char = pad_char;
+        self
+    }
+
+    /// Cut values longer than their column instead of failing
+    pub fn with_truncation(mut self) -> Self {
+        self.truncate = true;
+        self
+    }
+
+    fn write_records(
+        &self,
+        output_path: &str,
+        columns: &[String],
+        rows: &[&[String]],
+    ) -> Result<()> {
+        if matches!(self.pad_char, '\n' | '\r') {
+            return Err(Error::ExportFailed(
+                "The pad character cannot be a line break".to_string(),
+            ));
+        }
+        let widths: Vec<usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                self.widths
+                    .iter()
+                    .find(|(name, _)| name == column)
+                    .map(|(_, width)| *width)
+                    .or(self.default_width)
+                    .unwrap_or_else(|| {
+                        rows.iter()
+                            .map(|row| row[i].chars().count())
+                            .max()
+                            .unwrap_or(0)
+                    })
+            })
+            .collect();
+
+        let file = File::create(output_path).map_err(|e| {
+            Error::ExportFailed(format!("Failed to create fixed-width file: {}", e))
+        })?;
+        let mut writer = BufWriter::new(file);
+        let write_error = |e: std::io::Error| {
+            Error::ExportFailed(format!("Failed to write fixed-width file: {}", e))
+        };
+
+        let mut line = String::new();
+        for (number, row) in rows.iter().enumerate() {
+            line.clear();
+            for ((column, width), value) in columns.iter().zip(&widths).zip(row.iter()) {
+                self.pad(&mut line, value, *width).map_err(|reason| {
+                    Error::ExportFailed(format!(
+                        "Record {} has a '{}' value that {}",
+                        number + 1,
+                        column,
+                        reason
+                    ))
+                })?;
+            }
+            writeln!(writer, "{}", line).map_err(write_error)?;
+        }
+
+        writer.flush().map_err(write_error)
+    }
+
+    /// Append `value` padded or truncated to `width` characters
+    fn pad(&self, line: &mut String, value: &str, width: usize) -> std::result::Result<(), String> {
+        if value.contains(['\n', '\r']) {
+            return Err("contains a line break".to_string());
+        }
+        let length = value.chars().count();
+        if length > width {
+            if !self.truncate {
+                return Err(format!(
+                    "is {} characters long, longer than its width of {}",
+                    length, width
+                ));
+            }
+            match self.alignment {
+                Alignment::Left => line.extend(value.chars().take(width)),
+                Alignment::Right => line.extend(value.chars().skip(length - width)),
+            }
+            return Ok(());
+        }
+        let padding = std::iter::repeat_n(self.pad_char, width - length);
+        match self.alignment {
+            Alignment::Left => {
+                line.push_str(value);
+                line.extend(padding);
+            }
+            Alignment::Right => {
+                line.extend(padding);
+                line.push_str(value);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for FixedWidthExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter for FixedWidthExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        let rows: Vec<&[String]> = data.chunks(1).collect();
+        self.write_records(output_path, std::slice::from_ref(&self.column), &rows)
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        let rows: Vec<&[String]> = records.rows().iter().map(Vec::as_slice).collect();
+        self.write_records(output_path, records.columns(), &rows)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "fixed-width"
+    }
+}
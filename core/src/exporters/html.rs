@@ -0,0 +1,128 @@
+use super::Exporter;
+use crate::{Error, RecordSet, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Writes a standalone HTML page holding one table
+///
+/// `&`, `<`, `>`, `"` and `'` are written as character references, and cells
+/// keep their whitespace and line breaks, so values show exactly as generated
+/// when the page is opened in a browser.
+pub struct HtmlExporter {
+    headers: Vec<String>,
+    title: String,
+}
+
+impl HtmlExporter {
+    pub fn new() -> Self {
+        Self::with_headers(vec!["generated_data".to_string()])
+    }
+
+    pub fn with_headers(headers: Vec<String>) -> Self {
+        Self {
+            headers,
+            title: "Generated data".to_string(),
+        }
+    }
+
+    /// Title of the page
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    fn write_page<'a>(
+        &self,
+        output_path: &str,
+        columns: &[String],
+        rows: impl Iterator<Item = &'a [String]>,
+    ) -> Result<()> {
+        let file = File::create(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to create HTML file: {}", e)))?;
+        let mut writer = BufWriter::new(file);
+        let write_error =
+            |e: std::io::Error| Error::ExportFailed(format!("Failed to write HTML file: {}", e));
+
+        write!(
+            writer,
+            concat!(
+                "<!DOCTYPE html>\n",
+                "<html>\n",
+                "<head>\n",
+                "<meta charset=\"utf-8\">\n",
+                "<title>{}</title>\n",
+                "<style>\n",
+                "table {{ border-collapse: collapse; }}\n",
+                "th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: left; white-space: pre-wrap; }}\n",
+                "</style>\n",
+                "</head>\n",
+                "<body>\n",
+                "<table>\n",
+                "<thead>\n",
+            ),
+            escape(&self.title)
+        )
+        .map_err(write_error)?;
+        write_row(&mut writer, "th", columns).map_err(write_error)?;
+        writeln!(writer, "</thead>\n<tbody>").map_err(write_error)?;
+        for row in rows {
+            write_row(&mut writer, "td", row).map_err(write_error)?;
+        }
+        writeln!(writer, "</tbody>\n</table>\n</body>\n</html>").map_err(write_error)?;
+
+        writer.flush().map_err(write_error)
+    }
+}
+
+fn write_row(writer: &mut impl Write, cell: &str, values: &[String]) -> std::io::Result<()> {
+    write!(writer, "<tr>")?;
+    for value in values {
+        write!(writer, "<{}>{}</{}>", cell, escape(value), cell)?;
+    }
+    writeln!(writer, "</tr>")
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl Default for HtmlExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter for HtmlExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        if self.headers.len() != 1 {
+            return Err(Error::ExportFailed(format!(
+                "Values need exactly one HTML column, got {} headers",
+                self.headers.len()
+            )));
+        }
+        self.write_page(output_path, &self.headers, data.chunks(1))
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        self.write_page(
+            output_path,
+            records.columns(),
+            records.rows().iter().map(Vec::as_slice),
+        )
+    }
+
+    fn format_name(&self) -> &'static str {
+        "HTML"
+    }
+}
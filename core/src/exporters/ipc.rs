@@ -0,0 +1,278 @@
+use super::Exporter;
+use crate::{Error, RecordSet, Result};
+use arrow_array::{ArrayRef, LargeStringArray, RecordBatch, StringArray};
+use arrow_ipc::writer::{FileWriter, StreamWriter};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
+
+/// Rows per record batch unless configured
+const DEFAULT_BATCH_SIZE: usize = 1024 * 1024;
+
+/// Most bytes of text a `Utf8` column can hold, as its offsets are 32-bit
+const MAX_UTF8_BYTES: usize = i32::MAX as usize;
+
+/// Layout of an Arrow IPC file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrowIpcFormat {
+    /// Random-access file format, also known as Feather v2, which readers can
+    /// memory-map
+    #[default]
+    File,
+    /// Streaming format, read front to back as batches arrive
+    Stream,
+}
+
+impl ArrowIpcFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ArrowIpcFormat::File => "Arrow IPC file",
+            ArrowIpcFormat::Stream => "Arrow IPC stream",
+        }
+    }
+}
+
+/// Writes Apache Arrow IPC files with one string column per header
+///
+/// Rows are buffered until a record batch is full, so `writer` can stream
+/// batches as values are generated.
+///
+/// ```no_run
+/// use regex_data_gen_core::{ArrowIpcExporter, ArrowIpcFormat, Exporter};
+///
+/// let exporter = ArrowIpcExporter::with_headers(vec!["email".to_string()])
+///     .with_format(ArrowIpcFormat::Stream)
+///     .with_batch_size(65_536)
+///     .with_large_utf8();
+/// exporter.export(&["a@example.com".to_string()], "emails.arrows").unwrap();
+/// ```
+pub struct ArrowIpcExporter {
+    headers: Vec<String>,
+    format: ArrowIpcFormat,
+    batch_size: usize,
+    large_utf8: bool,
+}
+
+impl ArrowIpcExporter {
+    pub fn new() -> Self {
+        Self::with_headers(vec!["generated_data".to_string()])
+    }
+
+    pub fn with_headers(headers: Vec<String>) -> Self {
+        Self {
+            headers,
+            format: ArrowIpcFormat::default(),
+            batch_size: DEFAULT_BATCH_SIZE,
+            large_utf8: false,
+        }
+    }
+
+    pub fn with_format(mut self, format: ArrowIpcFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Rows per record batch
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Write `LargeUtf8` columns, with 64-bit offsets, instead of `Utf8`
+    ///
+    /// `Utf8` columns hold at most 2 GiB of text per batch, so batches are
+    /// written early when a column would grow past it, and a single longer
+    /// value fails the export.
+    pub fn with_large_utf8(mut self) -> Self {
+        self.large_utf8 = true;
+        self
+    }
+
+    /// Start streaming rows with a column per header to `writer`
+    pub fn writer<W: Write>(&self, writer: W) -> Result<ArrowIpcWriter<W>> {
+        ArrowIpcWriter::new(writer, &self.headers, self)
+    }
+
+    fn create(
+        &self,
+        output_path: &str,
+        columns: &[String],
+    ) -> Result<ArrowIpcWriter<BufWriter<File>>> {
+        let file = File::create(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to create Arrow file: {}", e)))?;
+        ArrowIpcWriter::new(BufWriter::new(file), columns, self)
+    }
+}
+
+impl Default for ArrowIpcExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+enum IpcWriter<W: Write> {
+    File(FileWriter<W>),
+    Stream(StreamWriter<W>),
+}
+
+/// Streams rows to a writer, one Arrow record batch at a time
+pub struct ArrowIpcWriter<W: Write> {
+    writer: IpcWriter<W>,
+    schema: SchemaRef,
+    /// Values of the batch being filled, one list per column
+    columns: Vec<Vec<String>>,
+    /// Bytes of text in each column of the batch being filled
+    column_bytes: Vec<usize>,
+    batch_size: usize,
+    large_utf8: bool,
+}
+
+impl<W: Write> ArrowIpcWriter<W> {
+    fn new(writer: W, columns: &[String], exporter: &ArrowIpcExporter) -> Result<Self> {
+        let data_type = if exporter.large_utf8 {
+            DataType::LargeUtf8
+        } else {
+            DataType::Utf8
+        };
+        let schema = Arc::new(Schema::new(
+            columns
+                .iter()
+                .map(|column| Field::new(column, data_type.clone(), false))
+                .collect::<Vec<_>>(),
+        ));
+        let writer = match exporter.format {
+            ArrowIpcFormat::File => {
+                IpcWriter::File(FileWriter::try_new(writer, &schema).map_err(arrow_error)?)
+            }
+            ArrowIpcFormat::Stream => {
+                IpcWriter::Stream(StreamWriter::try_new(writer, &schema).map_err(arrow_error)?)
+            }
+        };
+        Ok(Self {
+            writer,
+            schema,
+            columns: vec![Vec::new(); columns.len()],
+            column_bytes: vec![0; columns.len()],
+            batch_size: exporter.batch_size,
+            large_utf8: exporter.large_utf8,
+        })
+    }
+
+    /// Write a value to a single-column file
+    pub fn write(&mut self, value: &str) -> Result<()> {
+        self.write_record(&[value])
+    }
+
+    /// Write a row holding a value per column, in column order
+    pub fn write_record<S: AsRef<str>>(&mut self, row: &[S]) -> Result<()> {
+        if row.len() != self.columns.len() {
+            return Err(Error::ExportFailed(format!(
+                "Expected {} Arrow columns, got {}",
+                self.columns.len(),
+                row.len()
+            )));
+        }
+        if !self.large_utf8 {
+            self.make_room(row)?;
+        }
+        for (i, value) in row.iter().enumerate() {
+            self.columns[i].push(value.as_ref().to_string());
+            self.column_bytes[i] += value.as_ref().len();
+        }
+        if self.columns.first().map_or(0, Vec::len) == self.batch_size {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    /// Write the last batch and the file footer, and give back the writer
+    pub fn finish(mut self) -> Result<W> {
+        self.write_batch()?;
+        match self.writer {
+            IpcWriter::File(writer) => writer.into_inner(),
+            IpcWriter::Stream(writer) => writer.into_inner(),
+        }
+        .map_err(arrow_error)
+    }
+
+    /// Write the batch early if `row` would take a column past the text that
+    /// `Utf8` offsets can address
+    fn make_room<S: AsRef<str>>(&mut self, row: &[S]) -> Result<()> {
+        let longest = row.iter().map(|value| value.as_ref().len()).max();
+        if let Some(length) = longest.filter(|&length| length > MAX_UTF8_BYTES) {
+            return Err(Error::ExportFailed(format!(
+                "A value of {} bytes does not fit a Utf8 column; use LargeUtf8 columns (--large-utf8)",
+                length
+            )));
+        }
+        let overflows = self
+            .column_bytes
+            .iter()
+            .zip(row)
+            .any(|(bytes, value)| bytes + value.as_ref().len() > MAX_UTF8_BYTES);
+        if overflows {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    fn write_batch(&mut self) -> Result<()> {
+        if self.columns.first().is_none_or(Vec::is_empty) {
+            return Ok(());
+        }
+        self.column_bytes.fill(0);
+        let arrays: Vec<ArrayRef> = self
+            .columns
+            .iter_mut()
+            .map(|column| {
+                let values = std::mem::take(column);
+                if self.large_utf8 {
+                    Arc::new(LargeStringArray::from(values)) as ArrayRef
+                } else {
+                    Arc::new(StringArray::from(values)) as ArrayRef
+                }
+            })
+            .collect();
+        let batch = RecordBatch::try_new(self.schema.clone(), arrays).map_err(arrow_error)?;
+        match &mut self.writer {
+            IpcWriter::File(writer) => writer.write(&batch),
+            IpcWriter::Stream(writer) => writer.write(&batch),
+        }
+        .map_err(arrow_error)
+    }
+}
+
+fn arrow_error(e: ArrowError) -> Error {
+    Error::ExportFailed(format!("Failed to write Arrow file: {}", e))
+}
+
+impl Exporter for ArrowIpcExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        if self.headers.len() != 1 {
+            return Err(Error::ExportFailed(format!(
+                "Values need exactly one Arrow column, got {} headers",
+                self.headers.len()
+            )));
+        }
+        let mut writer = self.create(output_path, &self.headers)?;
+        for value in data {
+            writer.write(value)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        let mut writer = self.create(output_path, records.columns())?;
+        for row in records.rows() {
+            writer.write_record(row)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn format_name(&self) -> &'static str {
+        self.format.name()
+    }
+}
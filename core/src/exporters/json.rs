@@ -1,6 +1,6 @@
 use super::Exporter;
-use crate::{Error, Result};
-use serde_json::Value;
+use crate::{Error, RecordSet, Result};
+use serde_json::{Map, Value};
 use std::fs::File;
 use std::io::Write;
 
@@ -23,6 +23,20 @@ impl JsonExporter {
             array_format,
         }
     }
+
+    fn write_value(&self, file: &mut File, json_data: &Value) -> Result<()> {
+        let json_string = if self.pretty {
+            serde_json::to_string_pretty(json_data)
+        } else {
+            serde_json::to_string(json_data)
+        }
+        .map_err(|e| Error::ExportFailed(format!("Failed to serialize JSON: {}", e)))?;
+
+        file.write_all(json_string.as_bytes())
+            .map_err(|e| Error::ExportFailed(format!("Failed to write JSON file: {}", e)))?;
+
+        Ok(())
+    }
 }
 
 impl Default for JsonExporter {
@@ -31,40 +45,59 @@ impl Default for JsonExporter {
     }
 }
 
+/// Values as an array of strings, or of `{"id": ..., "value": ...}` objects
+pub(crate) fn values_document(data: &[String], array_format: bool) -> Value {
+    if array_format {
+        // Export as array of strings
+        Value::Array(data.iter().map(|s| Value::String(s.clone())).collect())
+    } else {
+        // Export as array of objects
+        Value::Array(
+            data.iter()
+                .enumerate()
+                .map(|(i, s)| {
+                    serde_json::json!({
+                        "id": i,
+                        "value": s
+                    })
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Records as an array of objects keyed by column name, in column order
+pub(crate) fn records_document(records: &RecordSet) -> Value {
+    Value::Array(
+        records
+            .rows()
+            .iter()
+            .map(|row| {
+                let object: Map<String, Value> = records
+                    .columns()
+                    .iter()
+                    .zip(row)
+                    .map(|(column, value)| (column.clone(), Value::String(value.clone())))
+                    .collect();
+                Value::Object(object)
+            })
+            .collect(),
+    )
+}
+
 impl Exporter for JsonExporter {
     fn export(&self, data: &[String], output_path: &str) -> Result<()> {
         let mut file = File::create(output_path)
             .map_err(|e| Error::ExportFailed(format!("Failed to create JSON file: {}", e)))?;
 
-        let json_data: Value = if self.array_format {
-            // Export as array of strings
-            Value::Array(data.iter().map(|s| Value::String(s.clone())).collect())
-        } else {
-            // Export as array of objects
-            Value::Array(
-                data.iter()
-                    .enumerate()
-                    .map(|(i, s)| {
-                        serde_json::json!({
-                            "id": i,
-                            "value": s
-                        })
-                    })
-                    .collect(),
-            )
-        };
-
-        let json_string = if self.pretty {
-            serde_json::to_string_pretty(&json_data)
-        } else {
-            serde_json::to_string(&json_data)
-        }
-        .map_err(|e| Error::ExportFailed(format!("Failed to serialize JSON: {}", e)))?;
+        self.write_value(&mut file, &values_document(data, self.array_format))
+    }
 
-        file.write_all(json_string.as_bytes())
-            .map_err(|e| Error::ExportFailed(format!("Failed to write JSON file: {}", e)))?;
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        let mut file = File::create(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to create JSON file: {}", e)))?;
 
-        Ok(())
+        self.write_value(&mut file, &records_document(records))
     }
 
     fn format_name(&self) -> &'static str {
@@ -0,0 +1,121 @@
+use super::Exporter;
+use crate::{Error, RecordSet, Result};
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Writes one JSON value per line (JSON Lines, also known as NDJSON)
+///
+/// Unlike `JsonExporter`, nothing is buffered beyond the current line, so
+/// `writer` can stream values to a file or pipe as they are generated.
+pub struct JsonLinesExporter {
+    string_format: bool,
+}
+
+impl JsonLinesExporter {
+    pub fn new() -> Self {
+        Self {
+            string_format: true,
+        }
+    }
+
+    /// Write each value as a JSON string when `string_format` is set, or as
+    /// an `{"id": ..., "value": ...}` object otherwise
+    pub fn with_options(string_format: bool) -> Self {
+        Self { string_format }
+    }
+
+    /// Start streaming lines to `writer`
+    pub fn writer<W: Write>(&self, writer: W) -> JsonLinesWriter<W> {
+        JsonLinesWriter {
+            writer,
+            string_format: self.string_format,
+            next_id: 0,
+        }
+    }
+
+    fn create(&self, output_path: &str) -> Result<JsonLinesWriter<BufWriter<File>>> {
+        let file = File::create(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to create JSON Lines file: {}", e)))?;
+        Ok(self.writer(BufWriter::new(file)))
+    }
+}
+
+impl Default for JsonLinesExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Streams values or records to a writer, one JSON line each
+pub struct JsonLinesWriter<W: Write> {
+    writer: W,
+    string_format: bool,
+    /// Index of the next value, used as its `id` in object format
+    next_id: usize,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    pub fn write(&mut self, value: &str) -> Result<()> {
+        let line = if self.string_format {
+            Value::String(value.to_string())
+        } else {
+            serde_json::json!({
+                "id": self.next_id,
+                "value": value
+            })
+        };
+        self.next_id += 1;
+        self.write_line(&line)
+    }
+
+    /// Write a record as an object keyed by column name, in column order
+    pub fn write_record(&mut self, columns: &[String], row: &[String]) -> Result<()> {
+        let object: Map<String, Value> = columns
+            .iter()
+            .zip(row)
+            .map(|(column, value)| (column.clone(), Value::String(value.clone())))
+            .collect();
+        self.write_line(&Value::Object(object))
+    }
+
+    /// Flush the lines written so far and give back the writer
+    pub fn finish(mut self) -> Result<W> {
+        self.writer
+            .flush()
+            .map_err(|e| Error::ExportFailed(format!("Failed to write JSON Lines file: {}", e)))?;
+        Ok(self.writer)
+    }
+
+    fn write_line(&mut self, line: &Value) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, line)
+            .map_err(|e| Error::ExportFailed(format!("Failed to serialize JSON: {}", e)))?;
+        self.writer
+            .write_all(b"\n")
+            .map_err(|e| Error::ExportFailed(format!("Failed to write JSON Lines file: {}", e)))
+    }
+}
+
+impl Exporter for JsonLinesExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        let mut writer = self.create(output_path)?;
+        for value in data {
+            writer.write(value)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        let mut writer = self.create(output_path)?;
+        for row in records.rows() {
+            writer.write_record(records.columns(), row)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn format_name(&self) -> &'static str {
+        "JSON Lines"
+    }
+}
@@ -0,0 +1,105 @@
+use super::Exporter;
+use crate::{Error, RecordSet, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Writes a GitHub-flavored Markdown pipe table, ready to paste into a doc
+///
+/// Characters Markdown would read as table structure or inline formatting,
+/// such as `|`, backticks, `*`, `_` and `<`, are escaped with a backslash so
+/// values render as generated. Line breaks become `<br>`, since a table row
+/// must fit on one line.
+pub struct MarkdownExporter {
+    headers: Vec<String>,
+}
+
+impl MarkdownExporter {
+    pub fn new() -> Self {
+        Self {
+            headers: vec!["generated_data".to_string()],
+        }
+    }
+
+    pub fn with_headers(headers: Vec<String>) -> Self {
+        Self { headers }
+    }
+
+    fn write_table<'a>(
+        &self,
+        output_path: &str,
+        columns: &[String],
+        rows: impl Iterator<Item = &'a [String]>,
+    ) -> Result<()> {
+        let file = File::create(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to create Markdown file: {}", e)))?;
+        let mut writer = BufWriter::new(file);
+        let write_error = |e: std::io::Error| {
+            Error::ExportFailed(format!("Failed to write Markdown file: {}", e))
+        };
+
+        write_row(&mut writer, columns).map_err(write_error)?;
+        let separator = vec!["---".to_string(); columns.len()];
+        writeln!(writer, "| {} |", separator.join(" | ")).map_err(write_error)?;
+        for row in rows {
+            write_row(&mut writer, row).map_err(write_error)?;
+        }
+
+        writer.flush().map_err(write_error)
+    }
+}
+
+fn write_row(writer: &mut impl Write, cells: &[String]) -> std::io::Result<()> {
+    let cells: Vec<String> = cells.iter().map(|cell| escape_cell(cell)).collect();
+    writeln!(writer, "| {} |", cells.join(" | "))
+}
+
+fn escape_cell(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' | '|' | '`' | '*' | '_' | '~' | '[' | ']' | '<' | '>' | '&' | '$' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\r' => {
+                // A CRLF pair is a single line break
+                chars.next_if_eq(&'\n');
+                escaped.push_str("<br>");
+            }
+            '\n' => escaped.push_str("<br>"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl Default for MarkdownExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter for MarkdownExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        if self.headers.len() != 1 {
+            return Err(Error::ExportFailed(format!(
+                "Values need exactly one Markdown column, got {} headers",
+                self.headers.len()
+            )));
+        }
+        self.write_table(output_path, &self.headers, data.chunks(1))
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        self.write_table(
+            output_path,
+            records.columns(),
+            records.rows().iter().map(Vec::as_slice),
+        )
+    }
+
+    fn format_name(&self) -> &'static str {
+        "Markdown"
+    }
+}
@@ -1,16 +1,69 @@
+#[cfg(feature = "avro")]
+pub mod avro;
+#[cfg(any(feature = "msgpack", feature = "cbor", feature = "bson"))]
+pub mod binary;
+pub mod config;
 pub mod csv;
+pub mod fixed;
+pub mod html;
+#[cfg(feature = "arrow")]
+pub mod ipc;
 pub mod json;
+pub mod jsonl;
+pub mod markdown;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod sql;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod tsv;
 pub mod xml;
 
-use crate::Result;
+use crate::{Error, RecordSet, Result};
 
 pub trait Exporter {
     fn export(&self, data: &[String], output_path: &str) -> Result<()>;
+
+    /// Export multi-column records as rows, objects or elements
+    ///
+    /// Exporters without record support export single-column records as
+    /// plain values and reject any others.
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        if records.columns().len() != 1 {
+            return Err(Error::ExportFailed(format!(
+                "{} export does not support records with {} columns",
+                self.format_name(),
+                records.columns().len()
+            )));
+        }
+        let values: Vec<String> = records.rows().iter().map(|row| row[0].clone()).collect();
+        self.export(&values, output_path)
+    }
+
     fn format_name(&self) -> &'static str;
 }
 
+#[cfg(feature = "avro")]
+pub use avro::{AvroCompression, AvroExporter, AvroWriter};
+#[cfg(feature = "bson")]
+pub use binary::BsonExporter;
+#[cfg(feature = "cbor")]
+pub use binary::CborExporter;
+#[cfg(feature = "msgpack")]
+pub use binary::MessagePackExporter;
+pub use config::{DocumentLayout, TomlExporter, YamlExporter};
 pub use csv::CsvExporter;
+pub use fixed::{Alignment, FixedWidthExporter};
+pub use html::HtmlExporter;
+#[cfg(feature = "arrow")]
+pub use ipc::{ArrowIpcExporter, ArrowIpcFormat, ArrowIpcWriter};
 pub use json::JsonExporter;
+pub use jsonl::{JsonLinesExporter, JsonLinesWriter};
+pub use markdown::MarkdownExporter;
+#[cfg(feature = "parquet")]
+pub use parquet::{ParquetCompression, ParquetExporter, ParquetWriter};
+pub use sql::{SqlDialect, SqlExporter};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteExporter;
 pub use tsv::TsvExporter;
 pub use xml::XmlExporter;
@@ -0,0 +1,232 @@
+use super::Exporter;
+use crate::{Error, RecordSet, Result};
+use arrow_array::builder::StringBuilder;
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
+
+/// Rows per row group unless configured, as in the Parquet writer itself
+const DEFAULT_ROW_GROUP_SIZE: usize = 1024 * 1024;
+
+/// Codec compressing the column chunks of a Parquet file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParquetCompression {
+    #[default]
+    Snappy,
+    Zstd,
+}
+
+impl ParquetCompression {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParquetCompression::Snappy => "snappy",
+            ParquetCompression::Zstd => "zstd",
+        }
+    }
+
+    fn codec(&self) -> Compression {
+        match self {
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+        }
+    }
+}
+
+/// Writes Apache Parquet files with one string column per header
+///
+/// Rows are buffered until a row group is full, so `writer` can stream row
+/// groups to a file as values are generated.
+///
+/// ```no_run
+/// use regex_data_gen_core::{Exporter, ParquetCompression, ParquetExporter};
+///
+/// let exporter = ParquetExporter::with_headers(vec!["email".to_string()])
+///     .with_row_group_size(100_000)
+///     .with_compression(ParquetCompression::Zstd);
+/// exporter.export(&["a@example.com".to_string()], "emails.parquet").unwrap();
+/// ```
+pub struct ParquetExporter {
+    headers: Vec<String>,
+    row_group_size: usize,
+    compression: ParquetCompression,
+}
+
+impl ParquetExporter {
+    pub fn new() -> Self {
+        Self::with_headers(vec!["generated_data".to_string()])
+    }
+
+    pub fn with_headers(headers: Vec<String>) -> Self {
+        Self {
+            headers,
+            row_group_size: DEFAULT_ROW_GROUP_SIZE,
+            compression: ParquetCompression::default(),
+        }
+    }
+
+    /// Rows per row group
+    pub fn with_row_group_size(mut self, row_group_size: usize) -> Self {
+        self.row_group_size = row_group_size.max(1);
+        self
+    }
+
+    pub fn with_compression(mut self, compression: ParquetCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Start streaming rows with a column per header to `writer`
+    pub fn writer<W: Write + Send>(&self, writer: W) -> Result<ParquetWriter<W>> {
+        ParquetWriter::new(writer, &self.headers, self.row_group_size, self.compression)
+    }
+
+    fn create(
+        &self,
+        output_path: &str,
+        columns: &[String],
+    ) -> Result<ParquetWriter<BufWriter<File>>> {
+        let file = File::create(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to create Parquet file: {}", e)))?;
+        ParquetWriter::new(
+            BufWriter::new(file),
+            columns,
+            self.row_group_size,
+            self.compression,
+        )
+    }
+}
+
+impl Default for ParquetExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Streams rows to a writer, one Parquet row group at a time
+pub struct ParquetWriter<W: Write + Send> {
+    writer: ArrowWriter<W>,
+    schema: SchemaRef,
+    /// Values of the row group being filled, one builder per column
+    columns: Vec<StringBuilder>,
+    buffered: usize,
+    row_group_size: usize,
+}
+
+impl<W: Write + Send> ParquetWriter<W> {
+    fn new(
+        writer: W,
+        columns: &[String],
+        row_group_size: usize,
+        compression: ParquetCompression,
+    ) -> Result<Self> {
+        let schema = Arc::new(Schema::new(
+            columns
+                .iter()
+                .map(|column| Field::new(column, DataType::Utf8, false))
+                .collect::<Vec<_>>(),
+        ));
+        let properties = WriterProperties::builder()
+            .set_compression(compression.codec())
+            .set_max_row_group_size(row_group_size)
+            .build();
+        let writer = ArrowWriter::try_new(writer, schema.clone(), Some(properties))
+            .map_err(parquet_error)?;
+        Ok(Self {
+            writer,
+            schema,
+            columns: columns.iter().map(|_| StringBuilder::new()).collect(),
+            buffered: 0,
+            row_group_size,
+        })
+    }
+
+    /// Write a value to a single-column file
+    pub fn write(&mut self, value: &str) -> Result<()> {
+        self.write_record(&[value])
+    }
+
+    /// Write a row holding a value per column, in column order
+    pub fn write_record<S: AsRef<str>>(&mut self, row: &[S]) -> Result<()> {
+        if row.len() != self.columns.len() {
+            return Err(Error::ExportFailed(format!(
+                "Expected {} Parquet columns, got {}",
+                self.columns.len(),
+                row.len()
+            )));
+        }
+        for (builder, value) in self.columns.iter_mut().zip(row) {
+            builder.append_value(value);
+        }
+        self.buffered += 1;
+        if self.buffered == self.row_group_size {
+            self.flush_row_group()?;
+        }
+        Ok(())
+    }
+
+    /// Write the last row group and the file footer, and give back the writer
+    pub fn finish(mut self) -> Result<W> {
+        self.flush_row_group()?;
+        let mut writer = self.writer.into_inner().map_err(parquet_error)?;
+        writer
+            .flush()
+            .map_err(|e| Error::ExportFailed(format!("Failed to write Parquet file: {}", e)))?;
+        Ok(writer)
+    }
+
+    fn flush_row_group(&mut self) -> Result<()> {
+        if self.buffered == 0 {
+            return Ok(());
+        }
+        let arrays: Vec<ArrayRef> = self
+            .columns
+            .iter_mut()
+            .map(|builder| Arc::new(builder.finish()) as ArrayRef)
+            .collect();
+        let batch = RecordBatch::try_new(self.schema.clone(), arrays)
+            .map_err(|e| Error::ExportFailed(format!("Failed to build Parquet rows: {}", e)))?;
+        self.writer.write(&batch).map_err(parquet_error)?;
+        self.writer.flush().map_err(parquet_error)?;
+        self.buffered = 0;
+        Ok(())
+    }
+}
+
+fn parquet_error(e: parquet::errors::ParquetError) -> Error {
+    Error::ExportFailed(format!("Failed to write Parquet file: {}", e))
+}
+
+impl Exporter for ParquetExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        if self.headers.len() != 1 {
+            return Err(Error::ExportFailed(format!(
+                "Values need exactly one Parquet column, got {} headers",
+                self.headers.len()
+            )));
+        }
+        let mut writer = self.create(output_path, &self.headers)?;
+        for value in data {
+            writer.write(value)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        let mut writer = self.create(output_path, records.columns())?;
+        for row in records.rows() {
+            writer.write_record(row)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn format_name(&self) -> &'static str {
+        "Parquet"
+    }
+}
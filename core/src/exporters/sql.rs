@@ -0,0 +1,230 @@
+use super::Exporter;
+use crate::{Error, RecordSet, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// SQL flavour deciding how identifiers are quoted and strings escaped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SqlDialect {
+    #[default]
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl SqlDialect {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "PostgreSQL",
+            SqlDialect::MySql => "MySQL",
+            SqlDialect::Sqlite => "SQLite",
+        }
+    }
+
+    /// Quote a table or column name, as `"name"` or, for MySQL, `` `name` ``
+    pub fn quote_identifier(&self, name: &str) -> String {
+        let quote = match self {
+            SqlDialect::MySql => '`',
+            SqlDialect::Postgres | SqlDialect::Sqlite => '"',
+        };
+        let escaped = name.replace(quote, &format!("{quote}{quote}"));
+        format!("{quote}{escaped}{quote}")
+    }
+
+    /// Write `value` as a string literal
+    ///
+    /// MySQL treats backslashes in literals as escapes, so they and control
+    /// characters are escaped there. PostgreSQL and SQLite literals only
+    /// double single quotes, and cannot hold NUL characters.
+    pub fn quote_string(&self, value: &str) -> Result<String> {
+        let mut quoted = String::with_capacity(value.len() + 2);
+        quoted.push('\'');
+        for c in value.chars() {
+            match (self, c) {
+                (_, '\'') => quoted.push_str("''"),
+                (SqlDialect::MySql, '\\') => quoted.push_str("\\\\"),
+                (SqlDialect::MySql, '\0') => quoted.push_str("\\0"),
+                (SqlDialect::MySql, '\n') => quoted.push_str("\\n"),
+                (SqlDialect::MySql, '\r') => quoted.push_str("\\r"),
+                (SqlDialect::MySql, '\x1A') => quoted.push_str("\\Z"),
+                (_, '\0') => {
+                    return Err(Error::ExportFailed(format!(
+                        "{} string literals cannot contain NUL characters",
+                        self.name()
+                    )));
+                }
+                _ => quoted.push(c),
+            }
+        }
+        quoted.push('\'');
+        Ok(quoted)
+    }
+
+    /// Column type for values of at most `max_length` characters
+    fn column_type(&self, max_length: Option<usize>) -> String {
+        // Longest VARCHAR each dialect accepts, in characters
+        let limit = match self {
+            SqlDialect::Postgres => 10_485_760,
+            // 65,535 bytes shared by the row, at up to 4 bytes per character
+            SqlDialect::MySql => 16_383,
+            SqlDialect::Sqlite => usize::MAX,
+        };
+        match max_length {
+            Some(length) if length <= limit => format!("VARCHAR({})", length.max(1)),
+            _ => "TEXT".to_string(),
+        }
+    }
+}
+
+/// Writes batched `INSERT INTO table (columns) VALUES ...` statements
+///
+/// ```no_run
+/// use regex_data_gen_core::{Exporter, SqlDialect, SqlExporter};
+///
+/// let exporter = SqlExporter::new(SqlDialect::MySql)
+///     .with_table("users")
+///     .with_column("email")
+///     .with_batch_size(500)
+///     .with_create_table()
+///     .with_max_length("email", 64);
+/// exporter.export(&["a@example.com".to_string()], "users.sql").unwrap();
+/// ```
+pub struct SqlExporter {
+    dialect: SqlDialect,
+    table: String,
+    column: String,
+    batch_size: usize,
+    create_table: bool,
+    max_lengths: Vec<(String, usize)>,
+}
+
+impl SqlExporter {
+    pub fn new(dialect: SqlDialect) -> Self {
+        Self {
+            dialect,
+            table: "generated_data".to_string(),
+            column: "generated_data".to_string(),
+            batch_size: 100,
+            create_table: false,
+            max_lengths: Vec::new(),
+        }
+    }
+
+    pub fn with_table(mut self, table: &str) -> Self {
+        self.table = table.to_string();
+        self
+    }
+
+    /// Column holding plain values; records use their own column names
+    pub fn with_column(mut self, column: &str) -> Self {
+        self.column = column.to_string();
+        self
+    }
+
+    /// Rows per `INSERT` statement
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Start with a `CREATE TABLE IF NOT EXISTS` statement
+    ///
+    /// Columns with a known maximum length are `VARCHAR(n)`, others `TEXT`.
+    pub fn with_create_table(mut self) -> Self {
+        self.create_table = true;
+        self
+    }
+
+    /// Declare that values of `column` have at most `max_length` characters,
+    /// as given by `DataGenerator::max_length`
+    pub fn with_max_length(mut self, column: &str, max_length: usize) -> Self {
+        self.max_lengths.push((column.to_string(), max_length));
+        self
+    }
+
+    fn write_statements<'a>(
+        &self,
+        output_path: &str,
+        columns: &[String],
+        rows: impl Iterator<Item = &'a [String]>,
+    ) -> Result<()> {
+        let file = File::create(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to create SQL file: {}", e)))?;
+        let mut writer = BufWriter::new(file);
+        let write_error =
+            |e: std::io::Error| Error::ExportFailed(format!("Failed to write SQL file: {}", e));
+
+        let table = self.dialect.quote_identifier(&self.table);
+        let quoted_columns: Vec<String> = columns
+            .iter()
+            .map(|column| self.dialect.quote_identifier(column))
+            .collect();
+
+        if self.create_table {
+            let definitions: Vec<String> = columns
+                .iter()
+                .zip(&quoted_columns)
+                .map(|(column, quoted)| {
+                    let max_length = self
+                        .max_lengths
+                        .iter()
+                        .find(|(name, _)| name == column)
+                        .map(|(_, length)| *length);
+                    format!("    {} {}", quoted, self.dialect.column_type(max_length))
+                })
+                .collect();
+            writeln!(
+                writer,
+                "CREATE TABLE IF NOT EXISTS {} (\n{}\n);\n",
+                table,
+                definitions.join(",\n")
+            )
+            .map_err(write_error)?;
+        }
+
+        let header = format!(
+            "INSERT INTO {} ({}) VALUES",
+            table,
+            quoted_columns.join(", ")
+        );
+        let mut in_batch = 0;
+        for row in rows {
+            let values = row
+                .iter()
+                .map(|value| self.dialect.quote_string(value))
+                .collect::<Result<Vec<_>>>()?;
+            let separator = if in_batch == 0 { header.as_str() } else { "," };
+            write!(writer, "{}\n    ({})", separator, values.join(", ")).map_err(write_error)?;
+
+            in_batch += 1;
+            if in_batch == self.batch_size {
+                writeln!(writer, ";").map_err(write_error)?;
+                in_batch = 0;
+            }
+        }
+        if in_batch > 0 {
+            writeln!(writer, ";").map_err(write_error)?;
+        }
+
+        writer.flush().map_err(write_error)
+    }
+}
+
+impl Exporter for SqlExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        let columns = [self.column.clone()];
+        self.write_statements(output_path, &columns, data.chunks(1))
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        self.write_statements(
+            output_path,
+            records.columns(),
+            records.rows().iter().map(Vec::as_slice),
+        )
+    }
+
+    fn format_name(&self) -> &'static str {
+        "SQL"
+    }
+}
@@ -0,0 +1,156 @@
+use super::{Exporter, SqlDialect};
+use crate::{Error, RecordSet, Result};
+use rusqlite::{Connection, params_from_iter};
+
+/// Inserts values or records straight into a SQLite database file
+///
+/// The table is created with `TEXT` columns when it does not exist yet, and
+/// rows are appended to it otherwise. All rows go in within one transaction,
+/// so an export either adds every row or none.
+///
+/// ```no_run
+/// use regex_data_gen_core::{Exporter, SqliteExporter};
+///
+/// let exporter = SqliteExporter::new()
+///     .with_table("users")
+///     .with_column("email")
+///     .with_unique_index("email");
+/// exporter.export(&["a@example.com".to_string()], "fixtures.db").unwrap();
+/// ```
+pub struct SqliteExporter {
+    table: String,
+    column: String,
+    unique_columns: Vec<String>,
+}
+
+impl SqliteExporter {
+    pub fn new() -> Self {
+        Self {
+            table: "generated_data".to_string(),
+            column: "generated_data".to_string(),
+            unique_columns: Vec::new(),
+        }
+    }
+
+    pub fn with_table(mut self, table: &str) -> Self {
+        self.table = table.to_string();
+        self
+    }
+
+    /// Column holding plain values; records use their own column names
+    pub fn with_column(mut self, column: &str) -> Self {
+        self.column = column.to_string();
+        self
+    }
+
+    /// Add a UNIQUE index on `column`, so duplicate values fail the export
+    pub fn with_unique_index(mut self, column: &str) -> Self {
+        self.unique_columns.push(column.to_string());
+        self
+    }
+
+    fn insert<'a>(
+        &self,
+        output_path: &str,
+        columns: &[String],
+        rows: impl Iterator<Item = &'a [String]>,
+    ) -> Result<()> {
+        if let Some(column) = self
+            .unique_columns
+            .iter()
+            .find(|column| !columns.contains(column))
+        {
+            return Err(Error::ExportFailed(format!(
+                "Cannot index unknown column '{}'",
+                column
+            )));
+        }
+
+        let mut connection = Connection::open(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to open SQLite database: {}", e)))?;
+        let sqlite_error =
+            |e: rusqlite::Error| Error::ExportFailed(format!("SQLite export failed: {}", e));
+        let dialect = SqlDialect::Sqlite;
+
+        let table = dialect.quote_identifier(&self.table);
+        let quoted_columns: Vec<String> = columns
+            .iter()
+            .map(|column| dialect.quote_identifier(column))
+            .collect();
+
+        let transaction = connection.transaction().map_err(sqlite_error)?;
+        let definitions: Vec<String> = quoted_columns
+            .iter()
+            .map(|column| format!("{} TEXT", column))
+            .collect();
+        transaction
+            .execute(
+                &format!(
+                    "CREATE TABLE IF NOT EXISTS {} ({})",
+                    table,
+                    definitions.join(", ")
+                ),
+                [],
+            )
+            .map_err(sqlite_error)?;
+
+        for column in &self.unique_columns {
+            let index = dialect.quote_identifier(&format!("{}_{}_unique", self.table, column));
+            transaction
+                .execute(
+                    &format!(
+                        "CREATE UNIQUE INDEX IF NOT EXISTS {} ON {} ({})",
+                        index,
+                        table,
+                        dialect.quote_identifier(column)
+                    ),
+                    [],
+                )
+                .map_err(sqlite_error)?;
+        }
+
+        {
+            let placeholders = vec!["?"; columns.len()].join(", ");
+            let mut statement = transaction
+                .prepare(&format!(
+                    "INSERT INTO {} ({}) VALUES ({})",
+                    table,
+                    quoted_columns.join(", "),
+                    placeholders
+                ))
+                .map_err(sqlite_error)?;
+            for row in rows {
+                statement
+                    .execute(params_from_iter(row))
+                    .map_err(sqlite_error)?;
+            }
+        }
+
+        transaction.commit().map_err(sqlite_error)
+    }
+}
+
+impl Default for SqliteExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter for SqliteExporter {
+    fn export(&self, data: &[String], output_path: &str) -> Result<()> {
+        let columns = [self.column.clone()];
+        self.insert(output_path, &columns, data.chunks(1))
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        self.insert(
+            output_path,
+            records.columns(),
+            records.rows().iter().map(Vec::as_slice),
+        )
+    }
+
+    fn format_name(&self) -> &'static str {
+        "SQLite"
+    }
+}
@@ -1,5 +1,5 @@
 use super::Exporter;
-use crate::{Error, Result};
+use crate::{Error, RecordSet, Result};
 use std::fs::File;
 use std::io::Write;
 
@@ -37,8 +37,24 @@ impl Exporter for TsvExporter {
 
         // Write data rows
         for item in data {
-            let escaped_item = item.replace('\t', "\\t").replace('\n', "\\n");
-            writeln!(file, "{}", escaped_item)
+            writeln!(file, "{}", escape_field(item))
+                .map_err(|e| Error::ExportFailed(format!("Failed to write TSV record: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        let mut file = File::create(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to create TSV file: {}", e)))?;
+
+        let header_line: Vec<String> = records.columns().iter().map(|c| escape_field(c)).collect();
+        writeln!(file, "{}", header_line.join("\t"))
+            .map_err(|e| Error::ExportFailed(format!("Failed to write TSV headers: {}", e)))?;
+
+        for row in records.rows() {
+            let fields: Vec<String> = row.iter().map(|v| escape_field(v)).collect();
+            writeln!(file, "{}", fields.join("\t"))
                 .map_err(|e| Error::ExportFailed(format!("Failed to write TSV record: {}", e)))?;
         }
 
@@ -49,3 +65,7 @@ impl Exporter for TsvExporter {
         "TSV"
     }
 }
+
+fn escape_field(value: &str) -> String {
+    value.replace('\t', "\\t").replace('\n', "\\n")
+}
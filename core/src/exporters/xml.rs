@@ -1,7 +1,8 @@
 use super::Exporter;
-use crate::{Error, Result};
+use crate::{Error, RecordSet, Result};
 use quick_xml::Writer;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use std::borrow::Cow;
 use std::fs::File;
 
 pub struct XmlExporter {
@@ -79,7 +80,83 @@ impl Exporter for XmlExporter {
         Ok(())
     }
 
+    fn export_records(&self, records: &RecordSet, output_path: &str) -> Result<()> {
+        let file = File::create(output_path)
+            .map_err(|e| Error::ExportFailed(format!("Failed to create XML file: {}", e)))?;
+
+        let mut writer = Writer::new(file);
+
+        writer
+            .write_event(Event::Decl(quick_xml::events::BytesDecl::new(
+                "1.0",
+                Some("UTF-8"),
+                None,
+            )))
+            .map_err(|e| Error::ExportFailed(format!("Failed to write XML declaration: {}", e)))?;
+
+        writer
+            .write_event(Event::Start(BytesStart::new(&self.root_element)))
+            .map_err(|e| Error::ExportFailed(format!("Failed to write root element: {}", e)))?;
+
+        // Each record becomes an item element with one child per column
+        for row in records.rows() {
+            writer
+                .write_event(Event::Start(BytesStart::new(&self.item_element)))
+                .map_err(|e| Error::ExportFailed(format!("Failed to write item start: {}", e)))?;
+
+            for (column, value) in records.columns().iter().zip(row) {
+                let name = element_name(column);
+                let mut element = writer.create_element(name.as_ref());
+                // Keep the column name when it had to be changed
+                if let Cow::Owned(_) = name {
+                    element = element.with_attribute(("name", column.as_str()));
+                }
+                element
+                    .write_text_content(BytesText::new(value))
+                    .map_err(|e| {
+                        Error::ExportFailed(format!("Failed to write field '{}': {}", column, e))
+                    })?;
+            }
+
+            writer
+                .write_event(Event::End(BytesEnd::new(&self.item_element)))
+                .map_err(|e| Error::ExportFailed(format!("Failed to write item end: {}", e)))?;
+        }
+
+        writer
+            .write_event(Event::End(BytesEnd::new(&self.root_element)))
+            .map_err(|e| Error::ExportFailed(format!("Failed to write root end: {}", e)))?;
+
+        Ok(())
+    }
+
     fn format_name(&self) -> &'static str {
         "XML"
     }
 }
+
+/// `column` as an XML element name
+///
+/// Characters a name cannot hold become `_`, and names that cannot start with
+/// their first character, such as `1id`, are prefixed with `_`. Colons are
+/// replaced too, since they would declare a namespace prefix.
+fn element_name(column: &str) -> Cow<'_, str> {
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '.');
+    let starts_name = column
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_');
+    if starts_name && column.chars().all(is_name_char) {
+        return Cow::Borrowed(column);
+    }
+    let mut name = String::with_capacity(column.len() + 1);
+    if !starts_name {
+        name.push('_');
+    }
+    name.extend(
+        column
+            .chars()
+            .map(|c| if is_name_char(c) { c } else { '_' }),
+    );
+    Cow::Owned(name)
+}
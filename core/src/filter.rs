@@ -0,0 +1,210 @@
+use crate::rewrite;
+use crate::{Error, Result};
+use regex_syntax::ParserBuilder;
+use regex_syntax::hir::{
+    Class, ClassBytes, ClassBytesRange, ClassUnicode, ClassUnicodeRange, Hir, HirKind,
+};
+use serde::Deserialize;
+
+/// Limits the characters that character classes of a pattern generate
+///
+/// A filter allows the union of its scripts, general categories and allowed
+/// characters, or every character when none are given, minus its denied
+/// characters. Patterns keep their full Unicode semantics: classes such as
+/// `\w` or `.` are intersected with the filter, while literal characters of
+/// the pattern are generated as written.
+///
+/// ```no_run
+/// use regex_data_gen_core::CharFilter;
+///
+/// // Latin and Cyrillic letters, digits, and no look-alike characters
+/// let filter = CharFilter::new()
+///     .script("Latin").unwrap()
+///     .script("Cyrillic").unwrap()
+///     .category("Nd").unwrap()
+///     .deny("0Oo1lI");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawCharFilter")]
+pub struct CharFilter {
+    /// Union of the allowed scripts, categories and characters, if any
+    allowed: Option<ClassUnicode>,
+    denied: ClassUnicode,
+}
+
+/// Schema representation of a `CharFilter`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawCharFilter {
+    #[serde(default)]
+    scripts: Vec<String>,
+    #[serde(default)]
+    categories: Vec<String>,
+    #[serde(default)]
+    allow: String,
+    #[serde(default)]
+    deny: String,
+}
+
+impl TryFrom<RawCharFilter> for CharFilter {
+    type Error = Error;
+
+    fn try_from(raw: RawCharFilter) -> Result<Self> {
+        let mut filter = CharFilter::new();
+        for script in &raw.scripts {
+            filter = filter.script(script)?;
+        }
+        for category in &raw.categories {
+            filter = filter.category(category)?;
+        }
+        if !raw.allow.is_empty() {
+            filter = filter.allow(&raw.allow);
+        }
+        Ok(filter.deny(&raw.deny))
+    }
+}
+
+impl Default for CharFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CharFilter {
+    /// A filter allowing every character
+    pub fn new() -> Self {
+        Self {
+            allowed: None,
+            denied: ClassUnicode::empty(),
+        }
+    }
+
+    /// Allow the characters of a Unicode script, such as `Latin`, `Cyrillic`,
+    /// `Han` or `Hangul`
+    pub fn script(self, name: &str) -> Result<Self> {
+        let class = property_class("sc", name)
+            .ok_or_else(|| Error::InvalidFilter(format!("Unknown script '{}'", name)))?;
+        Ok(self.allow_class(&class))
+    }
+
+    /// Allow the characters of a Unicode general category, such as `L`,
+    /// `Lu` or `Nd`
+    pub fn category(self, name: &str) -> Result<Self> {
+        let class = property_class("gc", name)
+            .ok_or_else(|| Error::InvalidFilter(format!("Unknown general category '{}'", name)))?;
+        Ok(self.allow_class(&class))
+    }
+
+    /// Allow each character of `chars`
+    pub fn allow(self, chars: &str) -> Self {
+        self.allow_class(&chars_class(chars))
+    }
+
+    /// Never generate any character of `chars` from a class
+    pub fn deny(mut self, chars: &str) -> Self {
+        self.denied.union(&chars_class(chars));
+        self
+    }
+
+    /// Whether the filter lets classes generate `c`
+    pub fn allows(&self, c: char) -> bool {
+        let contains = |class: &ClassUnicode| {
+            class
+                .ranges()
+                .iter()
+                .any(|r| r.start() <= c && c <= r.end())
+        };
+        self.allowed.as_ref().is_none_or(contains) && !contains(&self.denied)
+    }
+
+    fn allow_class(mut self, class: &ClassUnicode) -> Self {
+        self.allowed
+            .get_or_insert_with(ClassUnicode::empty)
+            .union(class);
+        self
+    }
+
+    /// Characters the filter lets through
+    fn class(&self) -> ClassUnicode {
+        let mut class = self
+            .allowed
+            .clone()
+            .unwrap_or_else(|| ClassUnicode::new([ClassUnicodeRange::new('\0', char::MAX)]));
+        class.difference(&self.denied);
+        class
+    }
+
+    /// Intersect every character class of `hir` with the filter
+    ///
+    /// Fails when a class has no characters left, since nothing could be
+    /// generated for it.
+    pub(crate) fn apply(&self, hir: Hir) -> Result<Hir> {
+        let allowed = self.class();
+        let allowed_bytes = ClassBytes::new(allowed.ranges().iter().filter_map(|r| {
+            let end = r.end().min('\x7F');
+            (r.start() <= end).then(|| ClassBytesRange::new(r.start() as u8, end as u8))
+        }));
+        rewrite::try_map_classes(hir, &mut |class| restrict(class, &allowed, &allowed_bytes))
+    }
+}
+
+fn restrict(class: Class, allowed: &ClassUnicode, allowed_bytes: &ClassBytes) -> Result<Hir> {
+    Ok(match class {
+        Class::Unicode(mut class) => {
+            let original = class.clone();
+            class.intersect(allowed);
+            if class.ranges().is_empty() {
+                return Err(no_characters_left(
+                    original.ranges().iter().map(|r| (r.start(), r.end())),
+                ));
+            }
+            Hir::class(Class::Unicode(class))
+        }
+        Class::Bytes(mut class) => {
+            let original = class.clone();
+            class.intersect(allowed_bytes);
+            if class.ranges().is_empty() {
+                return Err(no_characters_left(
+                    original
+                        .ranges()
+                        .iter()
+                        .map(|r| (char::from(r.start()), char::from(r.end()))),
+                ));
+            }
+            Hir::class(Class::Bytes(class))
+        }
+    })
+}
+
+fn no_characters_left(ranges: impl Iterator<Item = (char, char)>) -> Error {
+    let class: String = ranges
+        .take(8)
+        .map(|(start, end)| {
+            if start == end {
+                start.escape_debug().to_string()
+            } else {
+                format!("{}-{}", start.escape_debug(), end.escape_debug())
+            }
+        })
+        .collect();
+    Error::InvalidFilter(format!(
+        "No character of the class [{}] passes the filter",
+        class
+    ))
+}
+
+/// The class of a Unicode property, or `None` if the property is unknown
+fn property_class(property: &str, value: &str) -> Option<ClassUnicode> {
+    let hir = ParserBuilder::new()
+        .build()
+        .parse(&format!(r"\p{{{}={}}}", property, value))
+        .ok()?;
+    match hir.into_kind() {
+        HirKind::Class(Class::Unicode(class)) => Some(class),
+        _ => None,
+    }
+}
+
+fn chars_class(chars: &str) -> ClassUnicode {
+    ClassUnicode::new(chars.chars().map(|c| ClassUnicodeRange::new(c, c)))
+}
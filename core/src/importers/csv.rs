@@ -0,0 +1,55 @@
+use super::{ImportedValue, Importer};
+use crate::{Error, Result};
+use csv::Reader;
+
+pub struct CsvImporter {
+    column: String,
+}
+
+impl CsvImporter {
+    pub fn new() -> Self {
+        Self {
+            column: "generated_data".to_string(),
+        }
+    }
+
+    pub fn with_column(column: String) -> Self {
+        Self { column }
+    }
+}
+
+impl Default for CsvImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Importer for CsvImporter {
+    fn import(&self, input_path: &str) -> Result<Vec<ImportedValue>> {
+        let mut reader = Reader::from_path(input_path)
+            .map_err(|e| Error::ImportFailed(format!("Failed to open CSV file: {}", e)))?;
+
+        let headers = reader
+            .headers()
+            .map_err(|e| Error::ImportFailed(format!("Failed to read CSV headers: {}", e)))?;
+        let index = headers
+            .iter()
+            .position(|h| h == self.column)
+            .ok_or_else(|| Error::ImportFailed(format!("Column '{}' not found", self.column)))?;
+
+        let mut values = Vec::new();
+        for record in reader.records() {
+            let record = record
+                .map_err(|e| Error::ImportFailed(format!("Failed to read CSV record: {}", e)))?;
+            let line = record.position().map_or(0, |p| p.line() as usize);
+            let value = record.get(index).unwrap_or_default().to_string();
+            values.push(ImportedValue { line, value });
+        }
+
+        Ok(values)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "CSV"
+    }
+}
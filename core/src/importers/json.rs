@@ -0,0 +1,123 @@
+use super::{ImportedValue, Importer};
+use crate::{Error, Result};
+use serde_json::Value;
+use std::fs;
+
+pub struct JsonImporter {
+    field: String,
+}
+
+impl JsonImporter {
+    pub fn new() -> Self {
+        Self {
+            field: "value".to_string(),
+        }
+    }
+
+    /// Read `field` from each object; plain string elements are read as-is
+    pub fn with_field(field: String) -> Self {
+        Self { field }
+    }
+
+    fn element_value(&self, element: Value) -> Result<String> {
+        match element {
+            Value::Object(mut object) => match object.remove(&self.field) {
+                Some(value) => self.element_value(value),
+                None => Err(Error::ImportFailed(format!(
+                    "Field '{}' not found",
+                    self.field
+                ))),
+            },
+            Value::String(s) => Ok(s),
+            Value::Null => Ok(String::new()),
+            Value::Array(_) => Err(Error::ImportFailed(
+                "Nested arrays are not supported".to_string(),
+            )),
+            other => Ok(other.to_string()),
+        }
+    }
+}
+
+impl Default for JsonImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Importer for JsonImporter {
+    fn import(&self, input_path: &str) -> Result<Vec<ImportedValue>> {
+        let content = fs::read_to_string(input_path)
+            .map_err(|e| Error::ImportFailed(format!("Failed to read JSON file: {}", e)))?;
+
+        let elements = match serde_json::from_str(&content) {
+            Ok(Value::Array(elements)) => elements,
+            Ok(_) => {
+                return Err(Error::ImportFailed(
+                    "Expected a top-level JSON array".to_string(),
+                ));
+            }
+            Err(e) => return Err(Error::ImportFailed(format!("Failed to parse JSON: {}", e))),
+        };
+
+        let lines = element_lines(&content);
+        elements
+            .into_iter()
+            .enumerate()
+            .map(|(i, element)| {
+                Ok(ImportedValue {
+                    line: lines.get(i).copied().unwrap_or(0),
+                    value: self.element_value(element)?,
+                })
+            })
+            .collect()
+    }
+
+    fn format_name(&self) -> &'static str {
+        "JSON"
+    }
+}
+
+/// Line on which each element of an already validated top-level array starts
+fn element_lines(content: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut line = 1;
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut expecting_element = false;
+
+    for c in content.chars() {
+        if c == '\n' {
+            line += 1;
+        }
+
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        if expecting_element && !c.is_whitespace() && c != ']' {
+            lines.push(line);
+            expecting_element = false;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '[' | '{' => {
+                depth += 1;
+                expecting_element = depth == 1 && c == '[';
+            }
+            ']' | '}' => depth -= 1,
+            ',' if depth == 1 => expecting_element = true,
+            _ => {}
+        }
+    }
+
+    lines
+}
@@ -0,0 +1,23 @@
+pub mod csv;
+pub mod json;
+pub mod tsv;
+pub mod xml;
+
+use crate::Result;
+
+/// A single value read from a dataset together with the line it starts on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedValue {
+    pub line: usize,
+    pub value: String,
+}
+
+pub trait Importer {
+    fn import(&self, input_path: &str) -> Result<Vec<ImportedValue>>;
+    fn format_name(&self) -> &'static str;
+}
+
+pub use csv::CsvImporter;
+pub use json::JsonImporter;
+pub use tsv::TsvImporter;
+pub use xml::XmlImporter;
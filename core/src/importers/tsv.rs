@@ -0,0 +1,59 @@
+use super::{ImportedValue, Importer};
+use crate::{Error, Result};
+use std::fs;
+
+pub struct TsvImporter {
+    column: String,
+}
+
+impl TsvImporter {
+    pub fn new() -> Self {
+        Self {
+            column: "generated_data".to_string(),
+        }
+    }
+
+    pub fn with_column(column: String) -> Self {
+        Self { column }
+    }
+}
+
+impl Default for TsvImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Importer for TsvImporter {
+    fn import(&self, input_path: &str) -> Result<Vec<ImportedValue>> {
+        let content = fs::read_to_string(input_path)
+            .map_err(|e| Error::ImportFailed(format!("Failed to read TSV file: {}", e)))?;
+
+        let mut lines = content.lines();
+        let header_line = lines
+            .next()
+            .ok_or_else(|| Error::ImportFailed("TSV file has no header line".to_string()))?;
+        let index = header_line
+            .split('\t')
+            .position(|h| h == self.column)
+            .ok_or_else(|| Error::ImportFailed(format!("Column '{}' not found", self.column)))?;
+
+        let mut values = Vec::new();
+        for (i, line) in lines.enumerate() {
+            // Undo the escaping applied by TsvExporter
+            let value = line
+                .split('\t')
+                .nth(index)
+                .unwrap_or_default()
+                .replace("\\t", "\t")
+                .replace("\\n", "\n");
+            values.push(ImportedValue { line: i + 2, value });
+        }
+
+        Ok(values)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "TSV"
+    }
+}
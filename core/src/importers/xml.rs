@@ -0,0 +1,97 @@
+use super::{ImportedValue, Importer};
+use crate::{Error, Result};
+use quick_xml::Reader;
+use quick_xml::escape::unescape;
+use quick_xml::events::Event;
+use std::fs;
+
+pub struct XmlImporter {
+    item_element: String,
+}
+
+impl XmlImporter {
+    pub fn new() -> Self {
+        Self {
+            item_element: "item".to_string(),
+        }
+    }
+
+    pub fn with_element(item_element: String) -> Self {
+        Self { item_element }
+    }
+}
+
+impl Default for XmlImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Importer for XmlImporter {
+    fn import(&self, input_path: &str) -> Result<Vec<ImportedValue>> {
+        let content = fs::read_to_string(input_path)
+            .map_err(|e| Error::ImportFailed(format!("Failed to read XML file: {}", e)))?;
+
+        let mut reader = Reader::from_str(&content);
+        let mut values = Vec::new();
+        let mut current: Option<ImportedValue> = None;
+        let xml_error =
+            |e: &dyn std::fmt::Display| Error::ImportFailed(format!("Failed to parse XML: {}", e));
+        // Line of the current event, counted on from the previous event
+        let mut line = 1;
+        let mut counted = 0;
+
+        loop {
+            let position = reader.buffer_position() as usize;
+            line += content[counted..position].matches('\n').count();
+            counted = position;
+            match reader.read_event().map_err(|e| xml_error(&e))? {
+                Event::Start(e) if e.name().as_ref() == self.item_element.as_bytes() => {
+                    current = Some(ImportedValue {
+                        line,
+                        value: String::new(),
+                    });
+                }
+                Event::Empty(e) if e.name().as_ref() == self.item_element.as_bytes() => {
+                    values.push(ImportedValue {
+                        line,
+                        value: String::new(),
+                    });
+                }
+                Event::Text(e) => {
+                    if let Some(item) = current.as_mut() {
+                        let text = e.decode().map_err(|e| xml_error(&e))?;
+                        item.value
+                            .push_str(&unescape(&text).map_err(|e| xml_error(&e))?);
+                    }
+                }
+                Event::GeneralRef(e) => {
+                    if let Some(item) = current.as_mut() {
+                        let name = e.decode().map_err(|e| xml_error(&e))?;
+                        let reference = format!("&{};", name);
+                        item.value
+                            .push_str(&unescape(&reference).map_err(|e| xml_error(&e))?);
+                    }
+                }
+                Event::CData(e) => {
+                    if let Some(item) = current.as_mut() {
+                        item.value.push_str(&e.decode().map_err(|e| xml_error(&e))?);
+                    }
+                }
+                Event::End(e) if e.name().as_ref() == self.item_element.as_bytes() => {
+                    if let Some(item) = current.take() {
+                        values.push(item);
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(values)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "XML"
+    }
+}
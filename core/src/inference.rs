@@ -0,0 +1,232 @@
+use crate::{Error, Result};
+use std::collections::BTreeSet;
+
+/// Maximum number of distinct values that are turned into an alternation
+const ENUMERATION_LIMIT: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CharKind {
+    Digit,
+    Lower,
+    Upper,
+    Other(char),
+}
+
+impl CharKind {
+    fn of(c: char) -> Self {
+        if c.is_ascii_digit() {
+            CharKind::Digit
+        } else if c.is_ascii_lowercase() {
+            CharKind::Lower
+        } else if c.is_ascii_uppercase() {
+            CharKind::Upper
+        } else {
+            CharKind::Other(c)
+        }
+    }
+
+    fn class_item(&self) -> String {
+        match self {
+            CharKind::Digit => "0-9".to_string(),
+            CharKind::Lower => "a-z".to_string(),
+            CharKind::Upper => "A-Z".to_string(),
+            CharKind::Other(c) => regex_syntax::escape(&c.to_string()),
+        }
+    }
+}
+
+/// A run of characters sharing the same kind
+struct Token<'a> {
+    kind: CharKind,
+    text: &'a str,
+}
+
+pub(crate) fn infer_pattern(samples: &[String]) -> Result<String> {
+    if samples.is_empty() {
+        return Err(Error::InferenceFailed(
+            "At least one sample is required".to_string(),
+        ));
+    }
+
+    // Few distinct values that repeat are most likely an enumeration
+    let distinct: BTreeSet<&str> = samples.iter().map(String::as_str).collect();
+    if is_enumeration(distinct.len(), samples.len()) {
+        return Ok(alternation(&distinct));
+    }
+
+    let tokenized: Vec<Vec<Token>> = samples.iter().map(|s| tokenize(s)).collect();
+    let first_shape: Vec<CharKind> = tokenized[0].iter().map(|t| t.kind).collect();
+    let same_shape = tokenized.iter().all(|tokens| {
+        tokens
+            .iter()
+            .map(|t| t.kind)
+            .eq(first_shape.iter().copied())
+    });
+
+    if same_shape {
+        return Ok(infer_from_tokens(&tokenized, samples.len()));
+    }
+
+    let lengths: BTreeSet<usize> = samples.iter().map(|s| s.chars().count()).collect();
+    if lengths.len() == 1 {
+        return Ok(infer_per_position(samples));
+    }
+
+    Ok(infer_single_class(samples))
+}
+
+fn is_enumeration(distinct: usize, total: usize) -> bool {
+    distinct <= ENUMERATION_LIMIT && distinct * 2 <= total
+}
+
+fn alternation(values: &BTreeSet<&str>) -> String {
+    if values.len() == 1 {
+        let value = values.iter().next().copied().unwrap_or_default();
+        return escape_literal(value);
+    }
+
+    let escaped: Vec<String> = values.iter().map(|v| escape_literal(v)).collect();
+    format!("({})", escaped.join("|"))
+}
+
+fn tokenize(sample: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut current: Option<CharKind> = None;
+
+    for (i, c) in sample.char_indices() {
+        let kind = CharKind::of(c);
+        if let Some(prev) = current
+            && prev != kind
+        {
+            tokens.push(Token {
+                kind: prev,
+                text: &sample[start..i],
+            });
+            start = i;
+        }
+        current = Some(kind);
+    }
+
+    if let Some(kind) = current {
+        tokens.push(Token {
+            kind,
+            text: &sample[start..],
+        });
+    }
+
+    tokens
+}
+
+fn infer_from_tokens(tokenized: &[Vec<Token>], total: usize) -> String {
+    let mut pattern = String::new();
+
+    for position in 0..tokenized[0].len() {
+        let kind = tokenized[0][position].kind;
+        let texts: Vec<&str> = tokenized.iter().map(|t| t[position].text).collect();
+        let distinct: BTreeSet<&str> = texts.iter().copied().collect();
+        let (min, max) = char_length_range(&texts);
+
+        match kind {
+            CharKind::Digit => {
+                pattern.push_str("[0-9]");
+                pattern.push_str(&quantifier(min, max));
+            }
+            CharKind::Lower | CharKind::Upper => {
+                if is_enumeration(distinct.len(), total) {
+                    pattern.push_str(&alternation(&distinct));
+                } else {
+                    pattern.push_str(&format!("[{}]", kind.class_item()));
+                    pattern.push_str(&quantifier(min, max));
+                }
+            }
+            CharKind::Other(c) => {
+                if distinct.len() == 1 {
+                    pattern.push_str(&escape_literal(texts[0]));
+                } else {
+                    pattern.push_str(&escape_literal(&c.to_string()));
+                    pattern.push_str(&quantifier(min, max));
+                }
+            }
+        }
+    }
+
+    pattern
+}
+
+fn infer_per_position(samples: &[String]) -> String {
+    let columns: Vec<Vec<char>> = samples.iter().map(|s| s.chars().collect()).collect();
+    let length = columns[0].len();
+
+    let mut classes: Vec<String> = Vec::with_capacity(length);
+    for position in 0..length {
+        let kinds: BTreeSet<CharKind> = columns.iter().map(|c| CharKind::of(c[position])).collect();
+        classes.push(character_class(&kinds));
+    }
+
+    // Merge runs of identical classes into a single quantified class
+    let mut pattern = String::new();
+    let mut i = 0;
+    while i < classes.len() {
+        let mut run = 1;
+        while i + run < classes.len() && classes[i + run] == classes[i] {
+            run += 1;
+        }
+        pattern.push_str(&classes[i]);
+        pattern.push_str(&quantifier(run, run));
+        i += run;
+    }
+
+    pattern
+}
+
+fn infer_single_class(samples: &[String]) -> String {
+    let kinds: BTreeSet<CharKind> = samples
+        .iter()
+        .flat_map(|s| s.chars())
+        .map(CharKind::of)
+        .collect();
+    let lengths: Vec<&str> = samples.iter().map(String::as_str).collect();
+    let (min, max) = char_length_range(&lengths);
+
+    let class = if kinds.is_empty() {
+        String::new()
+    } else {
+        character_class(&kinds)
+    };
+
+    format!("{}{}", class, quantifier(min, max))
+}
+
+fn character_class(kinds: &BTreeSet<CharKind>) -> String {
+    if let [CharKind::Other(c)] = kinds.iter().copied().collect::<Vec<_>>().as_slice() {
+        return escape_literal(&c.to_string());
+    }
+
+    let items: String = kinds.iter().map(CharKind::class_item).collect();
+    format!("[{}]", items)
+}
+
+/// Escape text for use outside a character class, where `-` needs no escaping
+fn escape_literal(text: &str) -> String {
+    regex_syntax::escape(text).replace("\\-", "-")
+}
+
+fn char_length_range(texts: &[&str]) -> (usize, usize) {
+    let lengths = texts.iter().map(|t| t.chars().count());
+    let min = lengths.clone().min().unwrap_or(0);
+    let max = lengths.max().unwrap_or(0);
+    (min, max)
+}
+
+fn quantifier(min: usize, max: usize) -> String {
+    if min == max {
+        if min == 1 {
+            String::new()
+        } else {
+            format!("{{{}}}", min)
+        }
+    } else {
+        format!("{{{},{}}}", min, max)
+    }
+}
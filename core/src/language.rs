@@ -0,0 +1,190 @@
+use crate::patterns;
+use crate::{Error, Result};
+use regex_automata::dfa::{Automaton, StartKind, dense};
+use regex_automata::nfa::thompson;
+use regex_automata::util::primitives::StateID;
+use regex_automata::util::start;
+use regex_automata::{Anchored, MatchKind};
+use regex_syntax::hir::Hir;
+use std::collections::{HashMap, VecDeque};
+
+/// Upper bound on product states explored before giving up
+const MAX_PRODUCT_STATES: usize = 500_000;
+
+/// Deterministic automaton accepting exactly the strings a pattern generates
+///
+/// Unlike `RegexEngine::is_match`, acceptance is a full match: the whole input
+/// has to be produced by the pattern.
+pub(crate) struct Language {
+    dfa: dense::DFA<Vec<u32>>,
+    start: StateID,
+}
+
+impl Language {
+    pub(crate) fn from_pattern(pattern: &str) -> Result<Self> {
+        let hir = regex_syntax::Parser::new()
+            .parse(&patterns::expand(pattern)?)
+            .map_err(|e| Error::InvalidRegex(e.to_string()))?;
+        Self::from_hir(&hir)
+    }
+
+    pub(crate) fn from_hir(hir: &Hir) -> Result<Self> {
+        let nfa = thompson::Compiler::new()
+            .build_from_hir(hir)
+            .map_err(|e| Error::AnalysisFailed(e.to_string()))?;
+
+        // Every match kind is needed so that no accepting path is pruned
+        let dfa = dense::Builder::new()
+            .configure(
+                dense::Config::new()
+                    .match_kind(MatchKind::All)
+                    .start_kind(StartKind::Anchored),
+            )
+            .build_from_nfa(&nfa)
+            .map_err(|e| Error::AnalysisFailed(e.to_string()))?;
+
+        let start = dfa
+            .start_state(&start::Config::new().anchored(Anchored::Yes))
+            .map_err(|e| Error::AnalysisFailed(e.to_string()))?;
+
+        Ok(Self { dfa, start })
+    }
+
+    pub(crate) fn start(&self) -> StateID {
+        self.start
+    }
+
+    pub(crate) fn next(&self, state: StateID, byte: u8) -> StateID {
+        self.dfa.next_state(state, byte)
+    }
+
+    pub(crate) fn is_dead(&self, state: StateID) -> bool {
+        self.dfa.is_dead_state(state) || self.dfa.is_quit_state(state)
+    }
+
+    /// Whether input ending in `state` is accepted
+    pub(crate) fn is_accepting(&self, state: StateID) -> bool {
+        self.dfa.is_match_state(self.dfa.next_eoi_state(state))
+    }
+
+    pub(crate) fn accepts(&self, text: &str) -> bool {
+        let mut state = self.start;
+        for &byte in text.as_bytes() {
+            state = self.next(state, byte);
+            if self.is_dead(state) {
+                return false;
+            }
+        }
+        self.is_accepting(state)
+    }
+}
+
+/// Strings that separate the languages of two patterns
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatternDiff {
+    /// Strings accepted by the left pattern but not by the right one
+    pub only_left: Vec<String>,
+    /// Strings accepted by the right pattern but not by the left one
+    pub only_right: Vec<String>,
+}
+
+impl PatternDiff {
+    /// Both patterns accept exactly the same strings
+    pub fn is_equivalent(&self) -> bool {
+        self.only_left.is_empty() && self.only_right.is_empty()
+    }
+
+    /// Every string accepted by the left pattern is accepted by the right one
+    pub fn is_subset(&self) -> bool {
+        self.only_left.is_empty()
+    }
+
+    /// Every string accepted by the right pattern is accepted by the left one
+    pub fn is_superset(&self) -> bool {
+        self.only_right.is_empty()
+    }
+}
+
+/// Walk the product of both automata breadth first, collecting up to `limit`
+/// of the shortest witnesses on each side.
+///
+/// Each product state is expanded along at most `limit` distinct paths, so
+/// states reached by many strings still yield several different witnesses.
+pub(crate) fn diff(left: &Language, right: &Language, limit: usize) -> Result<PatternDiff> {
+    let limit = limit.max(1);
+    let mut result = PatternDiff::default();
+
+    // Each node remembers its parent and the byte that led to it
+    let mut nodes: Vec<(StateID, StateID, usize, u8)> = Vec::new();
+    let mut visits: HashMap<(StateID, StateID), usize> = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    let start = (left.start(), right.start());
+    nodes.push((start.0, start.1, usize::MAX, 0));
+    visits.insert(start, 1);
+    queue.push_back(0);
+
+    while let Some(index) = queue.pop_front() {
+        let (l, r, _, _) = nodes[index];
+        let left_accepts = left.is_accepting(l);
+        let right_accepts = right.is_accepting(r);
+
+        if left_accepts
+            && !right_accepts
+            && result.only_left.len() < limit
+            && let Some(witness) = witness(&nodes, index)
+        {
+            result.only_left.push(witness);
+        } else if right_accepts
+            && !left_accepts
+            && result.only_right.len() < limit
+            && let Some(witness) = witness(&nodes, index)
+        {
+            result.only_right.push(witness);
+        }
+
+        if result.only_left.len() >= limit && result.only_right.len() >= limit {
+            break;
+        }
+
+        for byte in 0..=u8::MAX {
+            let next = (left.next(l, byte), right.next(r, byte));
+            let left_dead = left.is_dead(next.0);
+            let right_dead = right.is_dead(next.1);
+
+            // Nothing left to find once a side can no longer accept
+            let right_full = result.only_right.len() >= limit;
+            let left_full = result.only_left.len() >= limit;
+            if (left_dead && (right_dead || right_full)) || (right_dead && left_full) {
+                continue;
+            }
+
+            let count = visits.entry(next).or_insert(0);
+            if *count >= limit {
+                continue;
+            }
+            *count += 1;
+
+            if nodes.len() >= MAX_PRODUCT_STATES {
+                return Err(Error::AnalysisFailed(format!(
+                    "Patterns are too complex to compare (more than {} states)",
+                    MAX_PRODUCT_STATES
+                )));
+            }
+            nodes.push((next.0, next.1, index, byte));
+            queue.push_back(nodes.len() - 1);
+        }
+    }
+
+    Ok(result)
+}
+
+fn witness(nodes: &[(StateID, StateID, usize, u8)], mut index: usize) -> Option<String> {
+    let mut bytes = Vec::new();
+    while nodes[index].2 != usize::MAX {
+        bytes.push(nodes[index].3);
+        index = nodes[index].2;
+    }
+    bytes.reverse();
+    String::from_utf8(bytes).ok()
+}
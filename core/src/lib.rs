@@ -1,10 +1,42 @@
+pub mod adversarial;
+pub mod checksum;
+mod combination;
 pub mod data_generator;
+pub mod datetime;
+pub mod dictionary;
 pub mod exporters;
+pub mod filter;
+pub mod importers;
+mod inference;
+pub mod language;
+pub mod numeric;
+pub mod patterns;
+pub mod records;
 pub mod regex_engine;
+mod rewrite;
+pub mod schema;
+pub mod template;
+pub mod transform;
 
-pub use data_generator::{DataGenerator, GenerationMode};
+pub use adversarial::{Trick, TrickyChar};
+pub use checksum::Checksum;
+pub use combination::CombinationRule;
+pub use data_generator::{DataGenerator, DataGeneratorBuilder, GenerationMode};
+pub use datetime::{DateTimeBound, DateTimeFormat, DateTimeSpec};
+pub use dictionary::{Dictionary, DictionarySource};
 pub use exporters::*;
+pub use filter::CharFilter;
+pub use importers::{CsvImporter, ImportedValue, Importer, JsonImporter, TsvImporter, XmlImporter};
+pub use language::PatternDiff;
+pub use numeric::NumericRange;
+pub use patterns::NamedPattern;
+pub use records::{DatabaseGenerator, GeneratedTable, RecordGenerator, RecordSet};
 pub use regex_engine::RegexEngine;
+pub use schema::{
+    Cardinality, ColumnRef, DatabaseSchema, FieldSpec, Schema, SchemaDocument, TableSchema,
+};
+pub use template::Template;
+pub use transform::Transform;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -14,6 +46,22 @@ pub enum Error {
     GenerationFailed(String),
     #[error("Export failed: {0}")]
     ExportFailed(String),
+    #[error("Import failed: {0}")]
+    ImportFailed(String),
+    #[error("Pattern inference failed: {0}")]
+    InferenceFailed(String),
+    #[error("Pattern analysis failed: {0}")]
+    AnalysisFailed(String),
+    #[error("Invalid schema: {0}")]
+    InvalidSchema(String),
+    #[error("Invalid template: {0}")]
+    InvalidTemplate(String),
+    #[error("Invalid date/time: {0}")]
+    InvalidDateTime(String),
+    #[error("Invalid numeric range: {0}")]
+    InvalidRange(String),
+    #[error("Invalid character filter: {0}")]
+    InvalidFilter(String),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -0,0 +1,218 @@
+use crate::{Error, Result};
+use rand::Rng;
+use rand::rngs::StdRng;
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Largest number of decimal places a range can use
+const MAX_SCALE: u32 = 18;
+
+/// An inclusive range of integers or fixed-point decimals for a capture group
+///
+/// Ranges are written `MIN..MAX`, optionally followed by `,step=STEP` and
+/// `,pad=WIDTH`, as in `0..255`, `0.00..99.95,step=0.05` or `1..999,pad=3`.
+/// Values use as many decimal places as the most precise of the bounds and
+/// step, and are zero-padded on the left to `pad` characters.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct NumericRange {
+    /// Bounds and step, scaled by `10^scale`
+    min: i128,
+    max: i128,
+    step: i128,
+    scale: u32,
+    width: Option<usize>,
+}
+
+impl NumericRange {
+    /// Every integer from `min` to `max`
+    pub fn integer(min: i64, max: i64) -> Result<Self> {
+        Self::new(&min.to_string(), &max.to_string())
+    }
+
+    /// Every value from `min` to `max` at the precision of the more precise bound
+    pub fn new(min: &str, max: &str) -> Result<Self> {
+        let scale = decimals(min)?.max(decimals(max)?);
+        let range = Self {
+            min: parse_scaled(min, scale)?,
+            max: parse_scaled(max, scale)?,
+            step: 10i128.pow(scale),
+            scale,
+            width: None,
+        };
+
+        if range.min > range.max {
+            return Err(Error::InvalidRange(format!(
+                "Minimum {} is greater than maximum {}",
+                min, max
+            )));
+        }
+        Ok(range)
+    }
+
+    /// Only allow values `min + k * step`
+    pub fn with_step(mut self, step: &str) -> Result<Self> {
+        let scale = self.scale.max(decimals(step)?);
+        self.rescale(scale)?;
+        self.step = parse_scaled(step, scale)?;
+
+        if self.step <= 0 {
+            return Err(Error::InvalidRange(format!(
+                "Step {} must be positive",
+                step
+            )));
+        }
+        Ok(self)
+    }
+
+    /// Pad values with leading zeros to at least `width` characters
+    pub fn zero_padded(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Whether `text` is a value of the range, written as the range writes it
+    pub fn contains(&self, text: &str) -> bool {
+        parse_scaled(text, self.scale).is_ok_and(|value| {
+            value >= self.min
+                && value <= self.max
+                && (value - self.min) % self.step == 0
+                && self.format(value) == text
+        })
+    }
+
+    pub(crate) fn sample(&self, rng: &mut StdRng) -> String {
+        let steps = (self.max - self.min) / self.step;
+        let value = self.min + rng.random_range(0..=steps) * self.step;
+        self.format(value)
+    }
+
+    fn rescale(&mut self, scale: u32) -> Result<()> {
+        let factor = 10i128.pow(scale - self.scale);
+        self.min = checked(self.min.checked_mul(factor))?;
+        self.max = checked(self.max.checked_mul(factor))?;
+        self.step = checked(self.step.checked_mul(factor))?;
+        self.scale = scale;
+        Ok(())
+    }
+
+    fn format(&self, value: i128) -> String {
+        let divisor = 10i128.pow(self.scale);
+        let magnitude = value.unsigned_abs();
+        let mut digits = (magnitude / divisor.unsigned_abs()).to_string();
+        if self.scale > 0 {
+            let fraction = magnitude % divisor.unsigned_abs();
+            digits = format!(
+                "{}.{:0scale$}",
+                digits,
+                fraction,
+                scale = self.scale as usize
+            );
+        }
+
+        let sign = if value < 0 { "-" } else { "" };
+        let width = self.width.unwrap_or(0).saturating_sub(sign.len());
+        format!("{}{:0>width$}", sign, digits, width = width)
+    }
+}
+
+impl FromStr for NumericRange {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let mut parts = spec.split(',').map(str::trim);
+        let bounds = parts.next().unwrap_or_default();
+        let Some((min, max)) = bounds.split_once("..") else {
+            return Err(Error::InvalidRange(format!(
+                "Expected MIN..MAX, got '{}'",
+                bounds
+            )));
+        };
+        let mut range = Self::new(min.trim(), max.trim_start_matches('=').trim())?;
+
+        for option in parts {
+            range = match option.split_once('=') {
+                Some(("step", step)) => range.with_step(step.trim())?,
+                Some(("pad", width)) => {
+                    range.zero_padded(width.trim().parse().map_err(|_| {
+                        Error::InvalidRange(format!("Invalid pad width '{}'", width))
+                    })?)
+                }
+                _ => {
+                    return Err(Error::InvalidRange(format!(
+                        "Unknown range option '{}', expected step=STEP or pad=WIDTH",
+                        option
+                    )));
+                }
+            };
+        }
+
+        Ok(range)
+    }
+}
+
+impl TryFrom<String> for NumericRange {
+    type Error = Error;
+
+    fn try_from(spec: String) -> Result<Self> {
+        spec.parse()
+    }
+}
+
+/// Number of digits after the decimal point of a number literal
+fn decimals(text: &str) -> Result<u32> {
+    let digits = text
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len()) as u32;
+    if digits > MAX_SCALE {
+        return Err(Error::InvalidRange(format!(
+            "'{}' has more than {} decimal places",
+            text, MAX_SCALE
+        )));
+    }
+    Ok(digits)
+}
+
+/// Parse a decimal literal into an integer scaled by `10^scale`
+fn parse_scaled(text: &str, scale: u32) -> Result<i128> {
+    let invalid = || Error::InvalidRange(format!("'{}' is not a number", text));
+
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty()
+        || !is_digits(whole)
+        || !is_digits(fraction)
+        || fraction.len() > scale as usize
+    {
+        return Err(invalid());
+    }
+
+    let padded = format!("{}{:0<scale$}", whole, fraction, scale = scale as usize);
+    let value: i128 = padded.parse().map_err(|_| invalid())?;
+    Ok(if negative { -value } else { value })
+}
+
+fn checked(value: Option<i128>) -> Result<i128> {
+    value.ok_or_else(|| Error::InvalidRange("Range is too large".to_string()))
+}
+
+/// Replace the markers returned by `rewrite::substitute_groups` with numbers
+pub(crate) fn fill_groups(
+    value: &str,
+    ranges: &[(String, NumericRange)],
+    markers: &[char],
+    rng: &mut StdRng,
+) -> String {
+    let mut filled = String::with_capacity(value.len());
+    for c in value.chars() {
+        match markers.iter().position(|&marker| marker == c) {
+            Some(i) => filled.push_str(&ranges[i].1.sample(rng)),
+            None => filled.push(c),
+        }
+    }
+    filled
+}
@@ -0,0 +1,166 @@
+use crate::{Error, Result};
+use std::borrow::Cow;
+
+/// A curated pattern that can be referred to by name
+///
+/// A whole pattern of `@name` is replaced by the named pattern, and `@{name}`
+/// embeds it inside a larger pattern, as in `user-@{uuid4}`. A whole pattern
+/// naming no library pattern, such as `@admin`, stays a literal regex. Library patterns
+/// have no anchors or capture groups so they can be embedded anywhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NamedPattern {
+    pub name: &'static str,
+    pub pattern: &'static str,
+    pub description: &'static str,
+}
+
+static LIBRARY: &[NamedPattern] = &[
+    NamedPattern {
+        name: "email",
+        pattern: r"[a-z0-9]{1,10}(?:[._-][a-z0-9]{1,10}){0,2}@[a-z0-9]{2,10}(?:-[a-z0-9]{2,10})?\.(?:com|org|net|io|dev)",
+        description: "Email address with a dot-atom local part",
+    },
+    NamedPattern {
+        name: "uuid",
+        pattern: "[0-9a-f]{8}-[0-9a-f]{4}-[1-5][0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}",
+        description: "RFC 4122 UUID of versions 1 to 5",
+    },
+    NamedPattern {
+        name: "uuid4",
+        pattern: "[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}",
+        description: "Random (version 4) UUID",
+    },
+    NamedPattern {
+        name: "ipv4",
+        pattern: r"(?:(?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])\.){3}(?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])",
+        description: "Dotted-decimal IPv4 address without leading zeros",
+    },
+    NamedPattern {
+        name: "ipv6",
+        pattern: "(?:[0-9a-f]{1,4}:){7}[0-9a-f]{1,4}",
+        description: "Uncompressed IPv6 address",
+    },
+    NamedPattern {
+        name: "mac",
+        pattern: "(?:[0-9A-F]{2}:){5}[0-9A-F]{2}",
+        description: "MAC address with colon separators",
+    },
+    NamedPattern {
+        name: "phone",
+        pattern: r"\+[1-9][0-9]{7,14}",
+        description: "E.164 phone number",
+    },
+    NamedPattern {
+        name: "phone_us",
+        pattern: r"\([2-9][0-9]{2}\) [2-9][0-9]{2}-[0-9]{4}",
+        description: "North American phone number, as (555) 555-0123",
+    },
+    NamedPattern {
+        name: "iso_date",
+        pattern: "(?:19|20)[0-9]{2}-(?:0[1-9]|1[0-2])-(?:0[1-9]|1[0-9]|2[0-8])",
+        description: "ISO 8601 calendar date; days stop at 28 so every month is valid",
+    },
+    NamedPattern {
+        name: "iso_time",
+        pattern: "(?:[01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9]",
+        description: "ISO 8601 time of day",
+    },
+    NamedPattern {
+        name: "iso_datetime",
+        pattern: "(?:19|20)[0-9]{2}-(?:0[1-9]|1[0-2])-(?:0[1-9]|1[0-9]|2[0-8])T(?:[01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9]Z",
+        description: "ISO 8601 UTC timestamp",
+    },
+    NamedPattern {
+        name: "url",
+        pattern: r"https?://[a-z0-9]{2,10}(?:-[a-z0-9]{2,10})?\.(?:com|org|net|io)(?:/[a-z0-9_-]{1,12}){0,3}",
+        description: "HTTP or HTTPS URL with an optional path",
+    },
+    NamedPattern {
+        name: "hex_color",
+        pattern: "#[0-9a-f]{6}",
+        description: "CSS hex color",
+    },
+    NamedPattern {
+        name: "semver",
+        pattern: r"(?:0|[1-9][0-9]{0,2})\.(?:0|[1-9][0-9]{0,2})\.(?:0|[1-9][0-9]{0,2})",
+        description: "Semantic version without pre-release or build metadata",
+    },
+    NamedPattern {
+        name: "zip_us",
+        pattern: "[0-9]{5}(?:-[0-9]{4})?",
+        description: "US ZIP or ZIP+4 code",
+    },
+];
+
+impl NamedPattern {
+    /// Every pattern of the library
+    pub fn all() -> &'static [NamedPattern] {
+        LIBRARY
+    }
+
+    /// The named pattern called `name`, without the leading `@`
+    pub fn lookup(name: &str) -> Option<&'static NamedPattern> {
+        LIBRARY.iter().find(|p| p.name == name)
+    }
+}
+
+/// Replace `@name` and embedded `@{name}` references with library patterns
+///
+/// Patterns without references are returned unchanged, as are whole patterns
+/// of `@name` whose name is not in the library. Unknown `@{name}` references
+/// are errors, since they are not valid regexes either.
+pub fn expand(pattern: &str) -> Result<Cow<'_, str>> {
+    if let Some(named) = pattern
+        .strip_prefix('@')
+        .filter(|name| is_name(name))
+        .and_then(NamedPattern::lookup)
+    {
+        return Ok(Cow::Borrowed(named.pattern));
+    }
+
+    if !pattern.contains("@{") {
+        return Ok(Cow::Borrowed(pattern));
+    }
+
+    let mut expanded = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find("@{") {
+        // An escaped `@` stays literal, as in `\@{2}`, but in `\\@{email}`
+        // the backslash is itself escaped
+        let backslashes = rest[..start]
+            .bytes()
+            .rev()
+            .take_while(|&b| b == b'\\')
+            .count();
+        let escaped = backslashes % 2 == 1;
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) if !escaped && is_name(&after[..end]) => {
+                expanded.push_str(&rest[..start]);
+                expanded.push_str("(?:");
+                expanded.push_str(resolve(&after[..end])?.pattern);
+                expanded.push(')');
+                rest = &after[end + 1..];
+            }
+            _ => {
+                expanded.push_str(&rest[..start + 2]);
+                rest = after;
+            }
+        }
+    }
+    expanded.push_str(rest);
+
+    Ok(Cow::Owned(expanded))
+}
+
+fn resolve(name: &str) -> Result<&'static NamedPattern> {
+    NamedPattern::lookup(name)
+        .ok_or_else(|| Error::InvalidRegex(format!("Unknown named pattern '@{}'", name)))
+}
+
+/// Names start with a lowercase letter, so repetitions like `@{2}` are left alone
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
@@ -0,0 +1,507 @@
+use crate::data_generator::ANY_PATTERN;
+use crate::schema::{ColumnRef, DatabaseSchema, FieldSpec, Schema, TableSchema};
+use crate::template::{Placeholder, Template};
+use crate::{DataGenerator, Error, GenerationMode, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use regex::Regex;
+
+/// Tabular generated data: named columns and rows of values
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordSet {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl RecordSet {
+    pub fn new(columns: Vec<String>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Build a single-column record set from plain generated values
+    pub fn single_column(column: &str, data: &[String]) -> Self {
+        Self {
+            columns: vec![column.to_string()],
+            rows: data.iter().map(|value| vec![value.clone()]).collect(),
+        }
+    }
+
+    pub fn push_row(&mut self, row: Vec<String>) -> Result<()> {
+        if row.len() != self.columns.len() {
+            return Err(Error::GenerationFailed(format!(
+                "Row has {} values but there are {} columns",
+                row.len(),
+                self.columns.len()
+            )));
+        }
+        self.rows.push(row);
+        Ok(())
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// Values of one column, if it exists
+    pub fn column(&self, name: &str) -> Option<Vec<&str>> {
+        let index = self.columns.iter().position(|c| c == name)?;
+        Some(self.rows.iter().map(|row| row[index].as_str()).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+enum FieldSource {
+    Pattern {
+        generator: Box<DataGenerator>,
+        mode: GenerationMode,
+    },
+    Reference(ColumnRef),
+    Template(Template),
+}
+
+struct FieldGenerator {
+    name: String,
+    source: FieldSource,
+    hidden: bool,
+    /// Anchored pattern used to extract capture groups for templates
+    captures: Option<Regex>,
+}
+
+/// Generates complete records from a schema, one generator per field
+pub struct RecordGenerator {
+    fields: Vec<FieldGenerator>,
+    default_count: Option<usize>,
+}
+
+impl RecordGenerator {
+    /// Create a record generator; fields are seeded from the schema seed, or
+    /// from a random base seed when the schema has none
+    pub fn from_schema(schema: &Schema) -> Result<Self> {
+        let base_seed = schema.seed.unwrap_or_else(rand::random);
+        Self::with_seed(schema, base_seed)
+    }
+
+    /// Create a record generator with an explicit base seed
+    pub fn with_seed(schema: &Schema, seed: u64) -> Result<Self> {
+        Self::from_fields(&schema.fields, schema.count, seed)
+    }
+
+    fn from_fields(fields: &[FieldSpec], default_count: Option<usize>, seed: u64) -> Result<Self> {
+        let fields = fields
+            .iter()
+            .map(|field| {
+                let source = match (field.column_ref(), &field.template) {
+                    (Some(reference), _) => FieldSource::Reference(reference?),
+                    (None, Some(template)) => FieldSource::Template(Template::parse(template)?),
+                    (None, None) => FieldSource::Pattern {
+                        generator: Box::new(Self::field_generator(field, seed)?),
+                        mode: field.mode,
+                    },
+                };
+                // The generator's pattern has library references and dictionaries expanded
+                let captures = match &source {
+                    FieldSource::Pattern { generator, .. } if field.pattern.is_some() => Some(
+                        Regex::new(&format!("^(?:{})$", generator.expanded_pattern()))
+                            .map_err(|e| Error::InvalidRegex(e.to_string()))?,
+                    ),
+                    _ => None,
+                };
+                Ok(FieldGenerator {
+                    name: field.name.clone(),
+                    source,
+                    hidden: field.hidden,
+                    captures,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let generator = Self {
+            fields,
+            default_count,
+        };
+        generator.validate_groups()?;
+        Ok(generator)
+    }
+
+    /// Check that every capture group used by a template exists in its field's pattern
+    fn validate_groups(&self) -> Result<()> {
+        for field in &self.fields {
+            let FieldSource::Template(template) = &field.source else {
+                continue;
+            };
+            for placeholder in template.placeholders() {
+                let Some(group) = &placeholder.group else {
+                    continue;
+                };
+                let target = self.fields.iter().find(|f| f.name == placeholder.field);
+                let exists = target
+                    .and_then(|f| f.captures.as_ref())
+                    .is_some_and(|regex| group_exists(regex, group));
+                if !exists {
+                    return Err(Error::InvalidSchema(format!(
+                        "Field '{}' uses unknown capture group '{}.{}'",
+                        field.name, placeholder.field, group
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn field_generator(field: &FieldSpec, seed: u64) -> Result<DataGenerator> {
+        let pattern = field.pattern.as_deref().unwrap_or(ANY_PATTERN);
+        let seed = seed.wrapping_add(field.seed_offset);
+        let mut builder = DataGenerator::builder(pattern)
+            .seed(seed)
+            .ascii_only(field.ascii_only)
+            .adversarial(field.adversarial);
+        if let Some(checksum) = field.checksum {
+            builder = builder.checksum(checksum);
+        }
+        if let Some(datetime) = &field.datetime {
+            builder = builder.datetime(datetime.clone());
+        }
+        for (group, range) in &field.ranges {
+            builder = builder.range(group, range.clone());
+        }
+        for (name, source) in &field.dictionaries {
+            let dictionary = source
+                .load()
+                .map_err(|e| Error::InvalidSchema(format!("Field '{}': {}", field.name, e)))?;
+            builder = builder.dictionary(name, dictionary);
+        }
+        if let Some(filter) = &field.filter {
+            builder = builder.filter(filter.clone());
+        }
+        for transform in &field.transforms {
+            builder = builder.transform(*transform);
+        }
+
+        builder
+            .build()
+            .map_err(|e| Error::InvalidSchema(format!("Field '{}': {}", field.name, e)))
+    }
+
+    /// Record count declared by the schema, if any
+    pub fn default_count(&self) -> Option<usize> {
+        self.default_count
+    }
+
+    /// Exported column names; hidden fields are left out
+    pub fn columns(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .filter(|f| !f.hidden)
+            .map(|f| f.name.clone())
+            .collect()
+    }
+
+    /// Greatest number of characters in values of `column`, if it is known
+    ///
+    /// Only fields generated from a pattern have a known length.
+    pub fn max_length(&self, column: &str) -> Option<usize> {
+        match &self.fields.iter().find(|f| f.name == column)?.source {
+            FieldSource::Pattern { generator, .. } => generator.max_length(),
+            FieldSource::Reference(_) | FieldSource::Template(_) => None,
+        }
+    }
+
+    /// Column of another table that `column` copies values from
+    fn reference(&self, column: &str) -> Option<&ColumnRef> {
+        match &self.fields.iter().find(|f| f.name == column)?.source {
+            FieldSource::Reference(reference) => Some(reference),
+            _ => None,
+        }
+    }
+
+    /// Fields that copy values from another table, with the column they copy
+    fn references(&self) -> impl Iterator<Item = (&str, &ColumnRef)> {
+        self.fields.iter().filter_map(|field| match &field.source {
+            FieldSource::Reference(reference) => Some((field.name.as_str(), reference)),
+            _ => None,
+        })
+    }
+
+    pub fn generate(&mut self, count: usize) -> Result<RecordSet> {
+        self.generate_with(count, |field, reference, _| {
+            Err(Error::GenerationFailed(format!(
+                "Field '{}' references '{}.{}', which needs a DatabaseGenerator",
+                field, reference.table, reference.column
+            )))
+        })
+    }
+
+    /// Generate records, asking `resolve` for the value of each reference
+    /// field in each row
+    fn generate_with<F>(&mut self, count: usize, mut resolve: F) -> Result<RecordSet>
+    where
+        F: FnMut(&str, &ColumnRef, usize) -> Result<String>,
+    {
+        let mut columns: Vec<Option<Vec<String>>> = Vec::with_capacity(self.fields.len());
+
+        for field in &mut self.fields {
+            let values = match &mut field.source {
+                FieldSource::Pattern { generator, mode } => {
+                    let values = generator.generate_with_mode(count, *mode)?;
+                    if values.len() < count {
+                        return Err(Error::GenerationFailed(format!(
+                            "Field '{}' produced only {} of {} values",
+                            field.name,
+                            values.len(),
+                            count
+                        )));
+                    }
+                    Some(values)
+                }
+                FieldSource::Reference(reference) => Some(
+                    (0..count)
+                        .map(|row| resolve(&field.name, reference, row))
+                        .collect::<Result<Vec<_>>>()?,
+                ),
+                FieldSource::Template(_) => None,
+            };
+            columns.push(values);
+        }
+
+        // Templates run last, in declaration order, so they can see every
+        // generated field and the templates declared before them
+        for (index, field) in self.fields.iter().enumerate() {
+            let FieldSource::Template(template) = &field.source else {
+                continue;
+            };
+            let values = (0..count)
+                .map(|row| template.render(|placeholder| self.lookup(&columns, placeholder, row)))
+                .collect::<Result<Vec<_>>>()?;
+            columns[index] = Some(values);
+        }
+
+        let mut visible: Vec<_> = self
+            .fields
+            .iter()
+            .zip(columns)
+            .filter(|(field, _)| !field.hidden)
+            .map(|(_, values)| values.unwrap_or_default().into_iter())
+            .collect();
+
+        let mut records = RecordSet::new(self.columns());
+        for _ in 0..count {
+            let row = visible.iter_mut().filter_map(Iterator::next).collect();
+            records.push_row(row)?;
+        }
+
+        Ok(records)
+    }
+
+    fn lookup(
+        &self,
+        columns: &[Option<Vec<String>>],
+        placeholder: &Placeholder,
+        row: usize,
+    ) -> Result<String> {
+        let unavailable =
+            || Error::GenerationFailed(format!("Field '{}' is not available", placeholder.field));
+        let index = self
+            .fields
+            .iter()
+            .position(|f| f.name == placeholder.field)
+            .ok_or_else(unavailable)?;
+        let value = columns[index].as_ref().ok_or_else(unavailable)?[row].as_str();
+
+        let Some(group) = &placeholder.group else {
+            return Ok(value.to_string());
+        };
+
+        let captures = self.fields[index]
+            .captures
+            .as_ref()
+            .and_then(|regex| regex.captures(value))
+            .ok_or_else(|| {
+                Error::GenerationFailed(format!(
+                    "Value '{}' of field '{}' does not match its pattern",
+                    value, placeholder.field
+                ))
+            })?;
+        let matched = match group.parse::<usize>() {
+            Ok(number) => captures.get(number),
+            Err(_) => captures.name(group),
+        };
+
+        Ok(matched.map(|m| m.as_str().to_string()).unwrap_or_default())
+    }
+}
+
+fn group_exists(regex: &Regex, group: &str) -> bool {
+    match group.parse::<usize>() {
+        Ok(number) => number < regex.captures_len(),
+        Err(_) => regex.capture_names().flatten().any(|name| name == group),
+    }
+}
+
+/// A generated table of a database schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedTable {
+    pub name: String,
+    /// File the schema asks this table to be exported to
+    pub output: Option<String>,
+    pub records: RecordSet,
+}
+
+struct TableGenerator {
+    schema: TableSchema,
+    records: RecordGenerator,
+}
+
+/// Generates several related tables whose reference fields point at values
+/// of previously generated tables
+pub struct DatabaseGenerator {
+    tables: Vec<TableGenerator>,
+    rng: StdRng,
+}
+
+impl DatabaseGenerator {
+    /// Create a database generator seeded from the schema seed, or from a
+    /// random base seed when the schema has none
+    pub fn from_schema(schema: &DatabaseSchema) -> Result<Self> {
+        let base_seed = schema.seed.unwrap_or_else(rand::random);
+        Self::with_seed(schema, base_seed)
+    }
+
+    /// Create a database generator with an explicit base seed
+    pub fn with_seed(schema: &DatabaseSchema, seed: u64) -> Result<Self> {
+        let tables = schema
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(i, table)| {
+                // Give every table its own seed space so equal patterns differ
+                let table_seed = seed.wrapping_add((i as u64) << 32);
+                let records = RecordGenerator::from_fields(&table.fields, table.count, table_seed)
+                    .map_err(|e| match e {
+                        Error::InvalidSchema(message) => {
+                            Error::InvalidSchema(format!("Table '{}': {}", table.name, message))
+                        }
+                        other => other,
+                    })?;
+                Ok(TableGenerator {
+                    schema: table.clone(),
+                    records,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            tables,
+            rng: StdRng::seed_from_u64(seed),
+        })
+    }
+
+    /// Greatest number of characters in values of `table.column`, following
+    /// references to the columns they copy
+    pub fn max_length(&self, table: &str, column: &str) -> Option<usize> {
+        let records = &self.tables.iter().find(|t| t.schema.name == table)?.records;
+        match records.reference(column) {
+            Some(reference) => self.max_length(&reference.table, &reference.column),
+            None => records.max_length(column),
+        }
+    }
+
+    /// Generate every table in declaration order
+    pub fn generate(&mut self) -> Result<Vec<GeneratedTable>> {
+        let mut generated: Vec<GeneratedTable> = Vec::with_capacity(self.tables.len());
+        let rng = &mut self.rng;
+
+        for table in &mut self.tables {
+            // Index of the parent row each child row belongs to
+            let parents: Option<(String, Vec<usize>)> = match &table.schema.per_parent {
+                Some(cardinality) => {
+                    let parent = find_table(&generated, &cardinality.table)?;
+                    let mut rows = Vec::new();
+                    for index in 0..parent.records.len() {
+                        let children = rng.random_range(cardinality.min..=cardinality.max);
+                        rows.extend(std::iter::repeat_n(index, children));
+                    }
+                    Some((cardinality.table.clone(), rows))
+                }
+                None => None,
+            };
+
+            let count = match (&parents, table.schema.count) {
+                (Some((_, rows)), _) => rows.len(),
+                (None, Some(count)) => count,
+                (None, None) => {
+                    return Err(Error::InvalidSchema(format!(
+                        "Table '{}' needs a count or per_parent",
+                        table.schema.name
+                    )));
+                }
+            };
+
+            // Find the rows and column of each reference once, not once per row
+            let mut targets = Vec::new();
+            for (field, reference) in table.records.references() {
+                let target = &find_table(&generated, &reference.table)?.records;
+                let column = target
+                    .columns()
+                    .iter()
+                    .position(|column| *column == reference.column)
+                    .ok_or_else(|| {
+                        Error::GenerationFailed(format!(
+                            "Field '{}' references unknown column '{}.{}'",
+                            field, reference.table, reference.column
+                        ))
+                    })?;
+                targets.push((field.to_string(), target.rows(), column));
+            }
+
+            let records = table
+                .records
+                .generate_with(count, |field, reference, row| {
+                    let Some((_, values, column)) = targets.iter().find(|(name, ..)| name == field)
+                    else {
+                        unreachable!("every reference field has a target");
+                    };
+
+                    let index = match &parents {
+                        Some((parent, rows)) if *parent == reference.table => rows[row],
+                        _ if values.is_empty() => {
+                            return Err(Error::GenerationFailed(format!(
+                                "Field '{}' references empty table '{}'",
+                                field, reference.table
+                            )));
+                        }
+                        _ => rng.random_range(0..values.len()),
+                    };
+                    Ok(values[index][*column].clone())
+                })?;
+
+            generated.push(GeneratedTable {
+                name: table.schema.name.clone(),
+                output: table.schema.output.clone(),
+                records,
+            });
+        }
+
+        Ok(generated)
+    }
+}
+
+fn find_table<'a>(tables: &'a [GeneratedTable], name: &str) -> Result<&'a GeneratedTable> {
+    tables
+        .iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| Error::GenerationFailed(format!("Table '{}' has not been generated", name)))
+}
@@ -1,31 +1,189 @@
+use crate::language::{self, Language, PatternDiff};
+use crate::numeric::NumericRange;
+use crate::patterns;
 use crate::{Error, Result};
 use regex::Regex;
+use regex_syntax::ParserBuilder;
+use regex_syntax::hir::{Hir, HirKind};
+use std::collections::HashSet;
+use std::sync::Arc;
 
 pub struct RegexEngine {
     pattern: String,
+    /// `pattern` with library references expanded
+    expanded: String,
     compiled: Regex,
+    ranges: Vec<(String, NumericRange)>,
+    entries: Vec<(String, Arc<HashSet<String>>)>,
+    /// `compiled` anchored at both ends
+    whole: Regex,
 }
 
 impl RegexEngine {
+    /// Compile a pattern, expanding `@name` references to library patterns
     pub fn new(pattern: &str) -> Result<Self> {
-        let compiled = Regex::new(pattern).map_err(|e| Error::InvalidRegex(e.to_string()))?;
+        let expanded = patterns::expand(pattern)?;
+        let compiled = Regex::new(&expanded).map_err(|e| Error::InvalidRegex(e.to_string()))?;
+        let whole = Regex::new(&format!("^(?:{})$", expanded))
+            .map_err(|e| Error::InvalidRegex(e.to_string()))?;
 
         Ok(Self {
             pattern: pattern.to_string(),
+            expanded: expanded.into_owned(),
             compiled,
+            ranges: Vec::new(),
+            entries: Vec::new(),
+            whole,
         })
     }
 
+    /// Require the named capture group `group` to hold a number of `range`
+    ///
+    /// `is_match` then only accepts matches whose group, when it participates,
+    /// is a value of the range. A group inside a repetition is checked at its
+    /// last repetition.
+    pub fn with_range(mut self, group: &str, range: NumericRange) -> Result<Self> {
+        self.check_group(group)?;
+        self.ranges.push((group.to_string(), range));
+        Ok(self)
+    }
+
+    /// Require the named capture group `group` to hold one of `entries`
+    ///
+    /// `is_match` then only accepts text that the whole pattern matches, with
+    /// the group, when it participates, holding one of the entries. A group
+    /// that could take text of varying length takes the shortest text that
+    /// lets the rest of the pattern match.
+    pub(crate) fn with_entries(
+        mut self,
+        group: &str,
+        entries: Arc<HashSet<String>>,
+    ) -> Result<Self> {
+        self.check_group(group)?;
+        self.entries.push((group.to_string(), entries));
+        Ok(self)
+    }
+
+    fn check_group(&self, group: &str) -> Result<()> {
+        if !self
+            .compiled
+            .capture_names()
+            .flatten()
+            .any(|name| name == group)
+        {
+            return Err(Error::InvalidRegex(format!(
+                "Pattern '{}' has no capture group named '{}'",
+                self.pattern, group
+            )));
+        }
+        Ok(())
+    }
+
+    /// Numeric ranges required of named capture groups
+    pub fn ranges(&self) -> &[(String, NumericRange)] {
+        &self.ranges
+    }
+
     pub fn pattern(&self) -> &str {
         &self.pattern
     }
 
+    /// The pattern with library references expanded
+    pub fn expanded_pattern(&self) -> &str {
+        &self.expanded
+    }
+
+    /// Greatest number of characters the pattern can generate, or `None` if
+    /// it is unbounded, as with `+` or `*`
+    pub fn max_length(&self) -> Option<usize> {
+        let hir = ParserBuilder::new().build().parse(&self.expanded).ok()?;
+        max_chars(&hir)
+    }
+
     pub fn is_match(&self, text: &str) -> bool {
-        self.compiled.is_match(text)
+        let regex = if self.entries.is_empty() {
+            &self.compiled
+        } else {
+            &self.whole
+        };
+        self.matches_groups(regex, text)
+    }
+
+    /// Whether the whole of `text`, not just part of it, matches the pattern
+    ///
+    /// Numeric ranges and entries of named groups are checked as by `is_match`.
+    pub fn is_full_match(&self, text: &str) -> bool {
+        self.matches_groups(&self.whole, text)
+    }
+
+    fn matches_groups(&self, regex: &Regex, text: &str) -> bool {
+        if self.ranges.is_empty() && self.entries.is_empty() {
+            return regex.is_match(text);
+        }
+
+        regex.captures_iter(text).any(|captures| {
+            self.ranges.iter().all(|(group, range)| {
+                captures
+                    .name(group)
+                    .is_none_or(|m| range.contains(m.as_str()))
+            }) && self.entries.iter().all(|(group, entries)| {
+                captures
+                    .name(group)
+                    .is_none_or(|m| entries.contains(m.as_str()))
+            })
+        })
     }
 
     pub fn validate_pattern(pattern: &str) -> Result<()> {
-        Regex::new(pattern).map_err(|e| Error::InvalidRegex(e.to_string()))?;
+        Regex::new(&patterns::expand(pattern)?).map_err(|e| Error::InvalidRegex(e.to_string()))?;
         Ok(())
     }
+
+    /// Propose a pattern that matches every example string
+    ///
+    /// Digit runs become fixed or bounded `[0-9]` repetitions, letters become
+    /// per-position classes, separators are kept as literals and small sets of
+    /// repeated values are turned into alternations.
+    pub fn infer_from_samples(samples: &[String]) -> Result<String> {
+        crate::inference::infer_pattern(samples)
+    }
+
+    /// Whether both patterns generate exactly the same set of strings
+    pub fn is_equivalent_to(&self, other: &RegexEngine) -> Result<bool> {
+        Ok(self.diff(other, 1)?.is_equivalent())
+    }
+
+    /// Whether every string generated by this pattern is also generated by `other`
+    pub fn is_subset_of(&self, other: &RegexEngine) -> Result<bool> {
+        Ok(self.diff(other, 1)?.is_subset())
+    }
+
+    /// Compare the languages of two patterns and collect up to `limit` of the
+    /// shortest strings accepted by one pattern but not the other, per side
+    ///
+    /// Both patterns are compiled to DFAs from their HIR, and the comparison uses
+    /// full-match semantics, so `a` and `a+` differ even though `is_match` agrees.
+    pub fn diff(&self, other: &RegexEngine, limit: usize) -> Result<PatternDiff> {
+        let left = Language::from_pattern(&self.expanded)?;
+        let right = Language::from_pattern(&other.expanded)?;
+        language::diff(&left, &right, limit)
+    }
+}
+
+fn max_chars(hir: &Hir) -> Option<usize> {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => Some(0),
+        HirKind::Literal(literal) => Some(String::from_utf8_lossy(&literal.0).chars().count()),
+        HirKind::Class(_) => Some(1),
+        HirKind::Repetition(repetition) => {
+            max_chars(&repetition.sub)?.checked_mul(repetition.max? as usize)
+        }
+        HirKind::Capture(capture) => max_chars(&capture.sub),
+        HirKind::Concat(subs) => subs
+            .iter()
+            .try_fold(0usize, |total, sub| total.checked_add(max_chars(sub)?)),
+        HirKind::Alternation(subs) => subs
+            .iter()
+            .try_fold(0, |longest, sub| Some(longest.max(max_chars(sub)?))),
+    }
 }
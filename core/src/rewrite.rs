@@ -0,0 +1,125 @@
+use crate::Error;
+use regex_syntax::hir::{
+    Capture, Class, ClassUnicode, ClassUnicodeRange, Hir, HirKind, Repetition,
+};
+use std::collections::HashSet;
+use std::convert::Infallible;
+
+/// Private-use characters that can stand in for a capture group
+const SENTINEL_BASE: u32 = 0xE000;
+const SENTINEL_END: u32 = 0xF8FF;
+
+/// Rebuild `hir` from the leaves up, passing every rebuilt node through `map`
+///
+/// `map` sees each node after its children were mapped, and what it returns
+/// is not walked again.
+pub(crate) fn try_map_nodes<E>(
+    hir: Hir,
+    map: &mut impl FnMut(Hir) -> Result<Hir, E>,
+) -> Result<Hir, E> {
+    let rebuilt = match hir.into_kind() {
+        HirKind::Capture(capture) => Hir::capture(Capture {
+            sub: Box::new(try_map_nodes(*capture.sub, map)?),
+            ..capture
+        }),
+        HirKind::Repetition(repetition) => Hir::repetition(Repetition {
+            sub: Box::new(try_map_nodes(*repetition.sub, map)?),
+            ..repetition
+        }),
+        HirKind::Concat(subs) => Hir::concat(
+            subs.into_iter()
+                .map(|sub| try_map_nodes(sub, map))
+                .collect::<Result<_, E>>()?,
+        ),
+        HirKind::Alternation(subs) => Hir::alternation(
+            subs.into_iter()
+                .map(|sub| try_map_nodes(sub, map))
+                .collect::<Result<_, E>>()?,
+        ),
+        HirKind::Empty => Hir::empty(),
+        HirKind::Literal(literal) => Hir::literal(literal.0),
+        HirKind::Class(class) => Hir::class(class),
+        HirKind::Look(look) => Hir::look(look),
+    };
+    map(rebuilt)
+}
+
+/// Rebuild `hir` with every character class replaced by `map` of it, stopping
+/// at the first error
+pub(crate) fn try_map_classes<E>(
+    hir: Hir,
+    map: &mut impl FnMut(Class) -> Result<Hir, E>,
+) -> Result<Hir, E> {
+    try_map_nodes(hir, &mut |node| match node.kind() {
+        HirKind::Class(class) => map(class.clone()),
+        _ => Ok(node),
+    })
+}
+
+/// Rebuild `hir` with every character class replaced by `map` of it
+pub(crate) fn map_classes(hir: Hir, map: &mut impl FnMut(Class) -> Hir) -> Hir {
+    let Ok(hir) = try_map_classes(hir, &mut |class| Ok::<_, Infallible>(map(class)));
+    hir
+}
+
+/// Replace every capture group named in `groups` with a private-use
+/// character, so that generated strings mark where each group's text goes
+///
+/// Returns the markers, one per group. They are characters the pattern never
+/// writes as literals, and are removed from every class, so that no generated
+/// character can be mistaken for a marker.
+pub(crate) fn substitute_groups(hir: Hir, groups: &[&str]) -> crate::Result<(Hir, Vec<char>)> {
+    let mut literals = HashSet::new();
+    collect_literals(&hir, &mut literals);
+    let markers: Vec<char> = (SENTINEL_BASE..=SENTINEL_END)
+        .filter_map(char::from_u32)
+        .filter(|c| !literals.contains(c))
+        .take(groups.len())
+        .collect();
+    if markers.len() < groups.len() {
+        return Err(Error::InvalidRegex(
+            "Too many numeric ranges and dictionary placeholders".to_string(),
+        ));
+    }
+
+    let excluded = ClassUnicode::new(markers.iter().map(|&c| ClassUnicodeRange::new(c, c)));
+    try_map_nodes(hir, &mut |node| match node.kind() {
+        HirKind::Capture(capture) => {
+            let position = capture
+                .name
+                .as_deref()
+                .and_then(|name| groups.iter().position(|group| *group == name));
+            Ok(match position {
+                Some(i) => Hir::literal(markers[i].to_string().into_bytes()),
+                None => node,
+            })
+        }
+        HirKind::Class(Class::Unicode(class)) => {
+            let mut class = class.clone();
+            class.difference(&excluded);
+            if class.ranges().is_empty() {
+                return Err(Error::InvalidRegex(
+                    "A character class only holds characters reserved for numeric ranges \
+                     and dictionaries"
+                        .to_string(),
+                ));
+            }
+            Ok(Hir::class(Class::Unicode(class)))
+        }
+        _ => Ok(node),
+    })
+    .map(|hir| (hir, markers))
+}
+
+/// Add every character `hir` writes as a literal to `literals`
+fn collect_literals(hir: &Hir, literals: &mut HashSet<char>) {
+    match hir.kind() {
+        HirKind::Literal(literal) => literals.extend(String::from_utf8_lossy(&literal.0).chars()),
+        HirKind::Capture(capture) => collect_literals(&capture.sub, literals),
+        HirKind::Repetition(repetition) => collect_literals(&repetition.sub, literals),
+        HirKind::Concat(subs) | HirKind::Alternation(subs) => {
+            subs.iter().for_each(|sub| collect_literals(sub, literals))
+        }
+        HirKind::Empty | HirKind::Class(_) | HirKind::Look(_) => {}
+    }
+}
@@ -0,0 +1,518 @@
+use crate::template::{Placeholder, Template};
+use crate::{
+    CharFilter, Checksum, DateTimeSpec, DictionarySource, Error, GenerationMode, NumericRange,
+    Result, Transform,
+};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Declarative description of a table of generated records
+///
+/// Schemas can be written in TOML, YAML or JSON:
+///
+/// ```toml
+/// count = 100
+/// seed = 42
+///
+/// [[fields]]
+/// name = "id"
+/// pattern = "[0-9]{6}"
+/// mode = "sequential"
+///
+/// [[fields]]
+/// name = "email"
+/// pattern = "[a-z]{5,10}@example\\.com"
+/// seed_offset = 1
+/// ascii_only = true
+///
+/// [[fields]]
+/// name = "card"
+/// pattern = "4[0-9]{15}"
+/// checksum = "luhn"
+///
+/// [[fields]]
+/// name = "age"
+/// pattern = "(?P<years>[0-9]{2})"
+/// ranges = { years = "18..99" }
+///
+/// [[fields]]
+/// name = "city"
+/// pattern = "{{dict:cities}}"
+/// dictionaries = { cities = { path = "cities.txt", unique = true } }
+///
+/// [[fields]]
+/// name = "username"
+/// pattern = "(?i)[a-z]{6,10}"
+/// transforms = ["random_case"]
+///
+/// [[fields]]
+/// name = "display_name"
+/// pattern = "\\w{4,12}"
+/// filter = { scripts = ["Latin", "Cyrillic"], deny = "_" }
+///
+/// [[fields]]
+/// name = "created_at"
+/// datetime = { format = "rfc3339", start = "2024-01-01", end = "2024-12-31", offset = "+02:00" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schema {
+    /// Number of records to generate when the caller does not override it
+    #[serde(default)]
+    pub count: Option<usize>,
+    /// Base seed shared by every field
+    #[serde(default)]
+    pub seed: Option<u64>,
+    pub fields: Vec<FieldSpec>,
+}
+
+/// A single column of a schema
+///
+/// Every field either generates values from `pattern` or `datetime`, copies
+/// values of another table's column named by `references`, such as
+/// `"users.id"`, or computes values from sibling fields with a `template`,
+/// such as `"{first_name|lower}.{last_name|lower}@example.com"`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldSpec {
+    pub name: String,
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub references: Option<String>,
+    #[serde(default)]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub mode: GenerationMode,
+    /// Added to the schema seed so that fields with equal patterns differ
+    #[serde(default)]
+    pub seed_offset: u64,
+    /// Restrict generated characters to ASCII
+    #[serde(default)]
+    pub ascii_only: bool,
+    /// Check digit scheme applied to generated values, such as `"luhn"`
+    #[serde(default)]
+    pub checksum: Option<Checksum>,
+    /// Generate real dates and times, optionally constrained by `pattern`
+    #[serde(default)]
+    pub datetime: Option<DateTimeSpec>,
+    /// Numeric ranges for named capture groups of `pattern`, such as
+    /// `{ age = "18..99" }`
+    #[serde(default)]
+    pub ranges: BTreeMap<String, NumericRange>,
+    /// Word lists filling `{{dict:name}}` placeholders of `pattern`
+    #[serde(default)]
+    pub dictionaries: BTreeMap<String, DictionarySource>,
+    /// Rewrites applied in order to generated values, such as `["lower", "nfc"]`
+    #[serde(default)]
+    pub transforms: Vec<Transform>,
+    /// Scripts, general categories and characters that classes of `pattern`
+    /// may generate
+    #[serde(default)]
+    pub filter: Option<CharFilter>,
+    /// Deliberately generate difficult Unicode characters the pattern allows
+    #[serde(default)]
+    pub adversarial: bool,
+    /// Generate the field for use in templates without exporting it
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// A `table.column` reference to values of another table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnRef {
+    pub table: String,
+    pub column: String,
+}
+
+/// Several related tables generated in one run
+///
+/// Tables are generated in declaration order, so a table can only reference
+/// tables declared before it:
+///
+/// ```toml
+/// seed = 42
+///
+/// [[tables]]
+/// name = "users"
+/// count = 10
+/// fields = [{ name = "id", pattern = "[0-9]{5}", mode = "sequential" }]
+///
+/// [[tables]]
+/// name = "orders"
+/// per_parent = { table = "users", min = 0, max = 5 }
+/// fields = [
+///     { name = "id", pattern = "[A-Z]{2}[0-9]{6}" },
+///     { name = "user_id", references = "users.id" },
+/// ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DatabaseSchema {
+    /// Base seed shared by every table
+    #[serde(default)]
+    pub seed: Option<u64>,
+    pub tables: Vec<TableSchema>,
+}
+
+/// One table of a database schema
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TableSchema {
+    pub name: String,
+    /// Number of records, required unless `per_parent` is set
+    #[serde(default)]
+    pub count: Option<usize>,
+    /// Generate a random number of records for every record of a parent table
+    #[serde(default)]
+    pub per_parent: Option<Cardinality>,
+    /// File the table is exported to
+    #[serde(default)]
+    pub output: Option<String>,
+    pub fields: Vec<FieldSpec>,
+}
+
+/// One-to-many relationship between a table and its parent
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Cardinality {
+    pub table: String,
+    #[serde(default)]
+    pub min: usize,
+    pub max: usize,
+}
+
+/// Either kind of schema file, told apart by a top-level `tables` key
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaDocument {
+    Table(Schema),
+    Database(DatabaseSchema),
+}
+
+impl Schema {
+    /// Load a schema, picking the format from the file extension
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_value(read_document(path.as_ref())?)
+    }
+
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        Self::from_value(parse_document(content, "toml")?)
+    }
+
+    pub fn from_yaml_str(content: &str) -> Result<Self> {
+        Self::from_value(parse_document(content, "yaml")?)
+    }
+
+    pub fn from_json_str(content: &str) -> Result<Self> {
+        Self::from_value(parse_document(content, "json")?)
+    }
+
+    /// Column names in declaration order
+    pub fn columns(&self) -> Vec<String> {
+        self.fields.iter().map(|f| f.name.clone()).collect()
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        let schema: Self = deserialize(value)?;
+        validate_fields(&schema.fields)?;
+
+        if let Some(field) = schema.fields.iter().find(|f| f.references.is_some()) {
+            return Err(Error::InvalidSchema(format!(
+                "Field '{}' uses references, which need a schema with tables",
+                field.name
+            )));
+        }
+
+        Ok(schema)
+    }
+}
+
+impl FieldSpec {
+    /// The referenced column, if this field copies values from another table
+    pub fn column_ref(&self) -> Option<Result<ColumnRef>> {
+        let reference = self.references.as_ref()?;
+        Some(match reference.split_once('.') {
+            Some((table, column)) if !table.is_empty() && !column.is_empty() => Ok(ColumnRef {
+                table: table.to_string(),
+                column: column.to_string(),
+            }),
+            _ => Err(Error::InvalidSchema(format!(
+                "Field '{}' must reference a column as 'table.column', got '{}'",
+                self.name, reference
+            ))),
+        })
+    }
+}
+
+impl DatabaseSchema {
+    /// Load a database schema, picking the format from the file extension
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_value(read_document(path.as_ref())?)
+    }
+
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        Self::from_value(parse_document(content, "toml")?)
+    }
+
+    pub fn from_yaml_str(content: &str) -> Result<Self> {
+        Self::from_value(parse_document(content, "yaml")?)
+    }
+
+    pub fn from_json_str(content: &str) -> Result<Self> {
+        Self::from_value(parse_document(content, "json")?)
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        let schema: Self = deserialize(value)?;
+        schema.validate()?;
+        Ok(schema)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.tables.is_empty() {
+            return Err(Error::InvalidSchema(
+                "Schema declares no tables".to_string(),
+            ));
+        }
+
+        for (i, table) in self.tables.iter().enumerate() {
+            let earlier = &self.tables[..i];
+            if table.name.is_empty() {
+                return Err(Error::InvalidSchema(format!("Table {} has no name", i + 1)));
+            }
+            if earlier.iter().any(|t| t.name == table.name) {
+                return Err(Error::InvalidSchema(format!(
+                    "Table '{}' is declared more than once",
+                    table.name
+                )));
+            }
+
+            validate_fields(&table.fields).map_err(|e| match e {
+                Error::InvalidSchema(message) => {
+                    Error::InvalidSchema(format!("Table '{}': {}", table.name, message))
+                }
+                other => other,
+            })?;
+
+            match &table.per_parent {
+                Some(cardinality) => {
+                    if !earlier.iter().any(|t| t.name == cardinality.table) {
+                        return Err(Error::InvalidSchema(format!(
+                            "Table '{}' must be declared after its parent '{}'",
+                            table.name, cardinality.table
+                        )));
+                    }
+                    if cardinality.min > cardinality.max {
+                        return Err(Error::InvalidSchema(format!(
+                            "Table '{}' has min {} greater than max {}",
+                            table.name, cardinality.min, cardinality.max
+                        )));
+                    }
+                }
+                None if table.count.is_none() => {
+                    return Err(Error::InvalidSchema(format!(
+                        "Table '{}' needs a count or per_parent",
+                        table.name
+                    )));
+                }
+                None => {}
+            }
+
+            for field in &table.fields {
+                let Some(reference) = field.column_ref() else {
+                    continue;
+                };
+                let reference = reference?;
+                let target = earlier
+                    .iter()
+                    .find(|t| t.name == reference.table)
+                    .and_then(|t| t.fields.iter().find(|f| f.name == reference.column));
+                match target {
+                    None => {
+                        return Err(Error::InvalidSchema(format!(
+                            "Field '{}.{}' references '{}.{}', which is not declared earlier",
+                            table.name, field.name, reference.table, reference.column
+                        )));
+                    }
+                    // Hidden fields are left out of the generated records
+                    Some(target) if target.hidden => {
+                        return Err(Error::InvalidSchema(format!(
+                            "Field '{}.{}' references '{}.{}', which is hidden",
+                            table.name, field.name, reference.table, reference.column
+                        )));
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SchemaDocument {
+    /// Load a single-table or database schema, picking the format from the file extension
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let value = read_document(path.as_ref())?;
+        if value.get("tables").is_some() {
+            Ok(SchemaDocument::Database(DatabaseSchema::from_value(value)?))
+        } else {
+            Ok(SchemaDocument::Table(Schema::from_value(value)?))
+        }
+    }
+}
+
+fn read_document(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| Error::InvalidSchema(format!("Failed to read '{}': {}", path.display(), e)))?;
+
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+
+    parse_document(&content, &extension)
+}
+
+/// Parse any supported format into a JSON value so that all formats share one
+/// deserialization and validation path
+fn parse_document(content: &str, format: &str) -> Result<Value> {
+    match format {
+        "toml" => toml::from_str(content)
+            .map_err(|e| Error::InvalidSchema(format!("Failed to parse TOML schema: {}", e))),
+        "yaml" | "yml" => serde_yaml::from_str(content)
+            .map_err(|e| Error::InvalidSchema(format!("Failed to parse YAML schema: {}", e))),
+        "json" => serde_json::from_str(content)
+            .map_err(|e| Error::InvalidSchema(format!("Failed to parse JSON schema: {}", e))),
+        _ => Err(Error::InvalidSchema(format!(
+            "Unsupported schema extension '{}', expected toml, yaml or json",
+            format
+        ))),
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(value: Value) -> Result<T> {
+    serde_json::from_value(value).map_err(|e| Error::InvalidSchema(e.to_string()))
+}
+
+fn validate_fields(fields: &[FieldSpec]) -> Result<()> {
+    if fields.is_empty() {
+        return Err(Error::InvalidSchema(
+            "Schema declares no fields".to_string(),
+        ));
+    }
+
+    for (i, field) in fields.iter().enumerate() {
+        if field.name.is_empty() {
+            return Err(Error::InvalidSchema(format!("Field {} has no name", i + 1)));
+        }
+        if fields[..i].iter().any(|f| f.name == field.name) {
+            return Err(Error::InvalidSchema(format!(
+                "Field '{}' is declared more than once",
+                field.name
+            )));
+        }
+        let sources = [
+            field.pattern.is_some() || field.datetime.is_some(),
+            field.references.is_some(),
+            field.template.is_some(),
+        ];
+        if sources.iter().filter(|&&s| s).count() != 1 {
+            return Err(Error::InvalidSchema(format!(
+                "Field '{}' needs exactly one of pattern (or datetime), references or template",
+                field.name
+            )));
+        }
+
+        if field.checksum.is_some() && field.pattern.is_none() {
+            return Err(Error::InvalidSchema(format!(
+                "Field '{}' has a checksum but no pattern",
+                field.name
+            )));
+        }
+        if !field.ranges.is_empty() && field.pattern.is_none() {
+            return Err(Error::InvalidSchema(format!(
+                "Field '{}' has numeric ranges but no pattern",
+                field.name
+            )));
+        }
+        if !field.dictionaries.is_empty() && field.pattern.is_none() {
+            return Err(Error::InvalidSchema(format!(
+                "Field '{}' has dictionaries but no pattern",
+                field.name
+            )));
+        }
+
+        if field.filter.is_some() && field.pattern.is_none() {
+            return Err(Error::InvalidSchema(format!(
+                "Field '{}' has a character filter but no pattern",
+                field.name
+            )));
+        }
+        if field.adversarial && field.pattern.is_none() {
+            return Err(Error::InvalidSchema(format!(
+                "Field '{}' is adversarial but has no pattern",
+                field.name
+            )));
+        }
+        if !field.transforms.is_empty() && field.pattern.is_none() && field.datetime.is_none() {
+            return Err(Error::InvalidSchema(format!(
+                "Field '{}' has transforms but no pattern or datetime",
+                field.name
+            )));
+        }
+
+        if let Some(source) = &field.template {
+            let template = Template::parse(source)
+                .map_err(|e| Error::InvalidSchema(format!("Field '{}': {}", field.name, e)))?;
+            for placeholder in template.placeholders() {
+                validate_placeholder(fields, i, placeholder)?;
+            }
+        }
+    }
+
+    if fields.iter().all(|f| f.hidden) {
+        return Err(Error::InvalidSchema(
+            "Schema declares no visible fields".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Templates may use any non-template field, and template fields declared
+/// before them; capture groups need a field generated from a pattern
+fn validate_placeholder(
+    fields: &[FieldSpec],
+    index: usize,
+    placeholder: &Placeholder,
+) -> Result<()> {
+    let field = &fields[index];
+    let Some(position) = fields.iter().position(|f| f.name == placeholder.field) else {
+        return Err(Error::InvalidSchema(format!(
+            "Field '{}' uses unknown field '{}'",
+            field.name, placeholder.field
+        )));
+    };
+
+    let target = &fields[position];
+    if target.template.is_some() && position >= index {
+        return Err(Error::InvalidSchema(format!(
+            "Field '{}' uses template field '{}', which must be declared before it",
+            field.name, placeholder.field
+        )));
+    }
+    if placeholder.group.is_some() && target.pattern.is_none() {
+        return Err(Error::InvalidSchema(format!(
+            "Field '{}' uses a capture group of '{}', which has no pattern",
+            field.name, placeholder.field
+        )));
+    }
+
+    Ok(())
+}
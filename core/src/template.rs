@@ -0,0 +1,188 @@
+use crate::checksum::luhn_check_digit;
+use crate::{Error, Result};
+
+/// A value computed from sibling fields of the same record
+///
+/// Placeholders are written as `{field}`, optionally naming a capture group of
+/// the field's pattern with `{field.group}` and piping the value through
+/// filters with `{field|lower|trim}`. Literal braces are written `{{` and `}}`.
+///
+/// Supported filters:
+/// - `lower`, `upper`: change case
+/// - `capitalize`: uppercase the first character and lowercase the rest
+/// - `trim`: strip surrounding whitespace
+/// - `digits`: keep only ASCII digits
+/// - `luhn`: append the Luhn check digit of the value's digits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// A `{field.group|filter}` reference inside a template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder {
+    pub field: String,
+    pub group: Option<String>,
+    filters: Vec<Filter>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Filter {
+    Lower,
+    Upper,
+    Capitalize,
+    Trim,
+    Digits,
+    Luhn,
+}
+
+impl Filter {
+    fn parse(name: &str) -> Result<Self> {
+        match name {
+            "lower" => Ok(Filter::Lower),
+            "upper" => Ok(Filter::Upper),
+            "capitalize" => Ok(Filter::Capitalize),
+            "trim" => Ok(Filter::Trim),
+            "digits" => Ok(Filter::Digits),
+            "luhn" => Ok(Filter::Luhn),
+            _ => Err(Error::InvalidTemplate(format!("Unknown filter '{}'", name))),
+        }
+    }
+
+    fn apply(&self, value: String) -> Result<String> {
+        match self {
+            Filter::Lower => Ok(value.to_lowercase()),
+            Filter::Upper => Ok(value.to_uppercase()),
+            Filter::Capitalize => {
+                let mut chars = value.chars();
+                Ok(match chars.next() {
+                    Some(first) => first
+                        .to_uppercase()
+                        .chain(chars.as_str().to_lowercase().chars())
+                        .collect(),
+                    None => value,
+                })
+            }
+            Filter::Trim => Ok(value.trim().to_string()),
+            Filter::Digits => Ok(value.chars().filter(char::is_ascii_digit).collect()),
+            Filter::Luhn => {
+                let check = luhn_check_digit(&value).ok_or_else(|| {
+                    Error::GenerationFailed(format!("Cannot compute a Luhn digit for '{}'", value))
+                })?;
+                Ok(format!("{}{}", value, check))
+            }
+        }
+    }
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = source.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut inner = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => inner.push(c),
+                            None => {
+                                return Err(Error::InvalidTemplate(format!(
+                                    "Unclosed placeholder in '{}'",
+                                    source
+                                )));
+                            }
+                        }
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Placeholder(Self::parse_placeholder(&inner)?));
+                }
+                '}' => {
+                    return Err(Error::InvalidTemplate(format!(
+                        "Unmatched '}}' in '{}'",
+                        source
+                    )));
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        Ok(Self { parts })
+    }
+
+    fn parse_placeholder(inner: &str) -> Result<Placeholder> {
+        let mut segments = inner.split('|').map(str::trim);
+        let reference = segments.next().unwrap_or_default();
+        let (field, group) = match reference.split_once('.') {
+            Some((field, group)) => (field, Some(group.to_string())),
+            None => (reference, None),
+        };
+
+        if field.is_empty() || group.as_deref() == Some("") {
+            return Err(Error::InvalidTemplate(format!(
+                "Invalid placeholder '{{{}}}'",
+                inner
+            )));
+        }
+
+        Ok(Placeholder {
+            field: field.to_string(),
+            group,
+            filters: segments.map(Filter::parse).collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    /// Every placeholder, in order of appearance
+    pub fn placeholders(&self) -> impl Iterator<Item = &Placeholder> {
+        self.parts.iter().filter_map(|part| match part {
+            Part::Placeholder(placeholder) => Some(placeholder),
+            Part::Literal(_) => None,
+        })
+    }
+
+    /// Render the template, asking `lookup` for the raw value of each placeholder
+    pub fn render<F>(&self, mut lookup: F) -> Result<String>
+    where
+        F: FnMut(&Placeholder) -> Result<String>,
+    {
+        let mut output = String::new();
+
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => output.push_str(text),
+                Part::Placeholder(placeholder) => {
+                    let mut value = lookup(placeholder)?;
+                    for filter in &placeholder.filters {
+                        value = filter.apply(value)?;
+                    }
+                    output.push_str(&value);
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
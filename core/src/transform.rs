@@ -0,0 +1,81 @@
+use rand::Rng;
+use rand::rngs::StdRng;
+use serde::Deserialize;
+use unicode_normalization::UnicodeNormalization;
+
+/// Rewrites applied to each generated value before it is checked
+///
+/// Transforms produce controlled variants of the same logical value, such as
+/// differently cased or normalized spellings. Values that no longer match the
+/// pattern after their transforms are regenerated, so case transforms suit
+/// case-insensitive patterns like `(?i)[a-z]+`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transform {
+    Upper,
+    Lower,
+    /// Uppercase the first letter of every word and lowercase the rest
+    Title,
+    /// Uppercase or lowercase each letter at random
+    RandomCase,
+    /// Unicode canonical composition
+    Nfc,
+    /// Unicode canonical decomposition
+    Nfd,
+    /// Unicode compatibility composition
+    Nfkc,
+    /// Unicode compatibility decomposition
+    Nfkd,
+}
+
+impl Transform {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transform::Upper => "upper",
+            Transform::Lower => "lower",
+            Transform::Title => "title",
+            Transform::RandomCase => "random_case",
+            Transform::Nfc => "NFC",
+            Transform::Nfd => "NFD",
+            Transform::Nfkc => "NFKC",
+            Transform::Nfkd => "NFKD",
+        }
+    }
+
+    pub(crate) fn apply(&self, value: &str, rng: &mut StdRng) -> String {
+        match self {
+            Transform::Upper => value.to_uppercase(),
+            Transform::Lower => value.to_lowercase(),
+            Transform::Title => title_case(value),
+            Transform::RandomCase => value
+                .chars()
+                .map(|c| {
+                    if rng.random_bool(0.5) {
+                        c.to_uppercase().collect::<String>()
+                    } else {
+                        c.to_lowercase().collect::<String>()
+                    }
+                })
+                .collect(),
+            Transform::Nfc => value.nfc().collect(),
+            Transform::Nfd => value.nfd().collect(),
+            Transform::Nfkc => value.nfkc().collect(),
+            Transform::Nfkd => value.nfkd().collect(),
+        }
+    }
+}
+
+/// Words are runs of alphanumeric characters and apostrophes
+fn title_case(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut in_word = false;
+    for c in value.chars() {
+        if in_word {
+            result.extend(c.to_lowercase());
+        } else {
+            result.extend(c.to_uppercase());
+        }
+        in_word = c.is_alphanumeric() || c == '\'';
+    }
+    result
+}
@@ -1,7 +1,26 @@
+#[cfg(feature = "bson")]
+use regex_data_gen_core::BsonExporter;
+#[cfg(feature = "cbor")]
+use regex_data_gen_core::CborExporter;
+#[cfg(feature = "msgpack")]
+use regex_data_gen_core::MessagePackExporter;
+#[cfg(feature = "sqlite")]
+use regex_data_gen_core::SqliteExporter;
 use regex_data_gen_core::{
-    DataGenerator, CsvExporter, JsonExporter, XmlExporter, TsvExporter,
-    Exporter, RegexEngine
+    Alignment, CharFilter, Checksum, CombinationRule, CsvExporter, CsvImporter, DataGenerator,
+    DatabaseGenerator, DatabaseSchema, DateTimeBound, DateTimeFormat, DateTimeSpec, Dictionary,
+    DocumentLayout, Exporter, FixedWidthExporter, GenerationMode, HtmlExporter, Importer,
+    JsonExporter, JsonImporter, JsonLinesExporter, MarkdownExporter, NamedPattern, NumericRange,
+    RecordGenerator, RegexEngine, Schema, SqlDialect, SqlExporter, Template, TomlExporter,
+    Transform, Trick, TsvExporter, TsvImporter, XmlExporter, XmlImporter, YamlExporter,
+    adversarial,
 };
+#[cfg(feature = "arrow")]
+use regex_data_gen_core::{ArrowIpcExporter, ArrowIpcFormat};
+#[cfg(feature = "avro")]
+use regex_data_gen_core::{AvroCompression, AvroExporter};
+#[cfg(feature = "parquet")]
+use regex_data_gen_core::{ParquetCompression, ParquetExporter};
 use std::fs;
 use tempfile::TempDir;
 
@@ -110,4 +129,2565 @@ fn test_multiple_format_exports() {
         assert!(!content.is_empty());
         assert_eq!(exporter.format_name(), format.to_uppercase());
     }
-}
\ No newline at end of file
+}
+#[test]
+fn test_infer_pattern_from_samples() {
+    let samples: Vec<String> = ["2023-01-15", "2024-11-02", "1999-07-30"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let pattern = RegexEngine::infer_from_samples(&samples).unwrap();
+    assert_eq!(pattern, "[0-9]{4}-[0-9]{2}-[0-9]{2}");
+
+    let samples: Vec<String> = ["AB-1234.com", "XY-42.org", "QQ-777.com", "ZZ-9.org"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let pattern = RegexEngine::infer_from_samples(&samples).unwrap();
+    assert_eq!(pattern, r"[A-Z]{2}-[0-9]{1,4}\.(com|org)");
+
+    // Inferred patterns feed straight back into generation
+    let anchored = RegexEngine::new(&format!("^(?:{})$", pattern)).unwrap();
+    let mut generator = DataGenerator::with_seed(&pattern, 7).unwrap();
+    for item in generator.generate(20).unwrap() {
+        assert!(anchored.is_match(&item));
+    }
+    for sample in &samples {
+        assert!(anchored.is_match(sample));
+    }
+}
+
+#[test]
+fn test_infer_pattern_fallbacks() {
+    let to_strings = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    // Repeated small set of values becomes an enumeration
+    let samples = to_strings(&["yes", "no", "yes", "no", "N/A", "yes"]);
+    assert_eq!(
+        RegexEngine::infer_from_samples(&samples).unwrap(),
+        r"(N/A|no|yes)"
+    );
+
+    // Same length but different shapes falls back to per-position classes
+    let samples = to_strings(&["a1", "1a", "b2"]);
+    assert_eq!(
+        RegexEngine::infer_from_samples(&samples).unwrap(),
+        "[0-9a-z]{2}"
+    );
+
+    assert!(RegexEngine::infer_from_samples(&[]).is_err());
+}
+
+#[test]
+fn test_pattern_equivalence_and_inclusion() {
+    let digits = RegexEngine::new(r"[0-9]{3}").unwrap();
+    let spelled = RegexEngine::new(r"[0-9][0-9][0-9]").unwrap();
+    let wider = RegexEngine::new(r"[0-9]{2,4}").unwrap();
+
+    assert!(digits.is_equivalent_to(&spelled).unwrap());
+    assert!(digits.is_subset_of(&wider).unwrap());
+    assert!(!wider.is_subset_of(&digits).unwrap());
+    assert!(!digits.is_equivalent_to(&wider).unwrap());
+}
+
+#[test]
+fn test_pattern_diff_witnesses() {
+    let old = RegexEngine::new(r"[a-z]+@[a-z]+\.(com|org)").unwrap();
+    let new = RegexEngine::new(r"[a-z]{2,}@[a-z]+\.com").unwrap();
+
+    let diff = old.diff(&new, 3).unwrap();
+    assert!(!diff.is_equivalent());
+    assert!(diff.is_superset());
+    assert_eq!(diff.only_left.len(), 3);
+    assert!(diff.only_right.is_empty());
+
+    // Every witness is accepted by the old pattern and rejected by the new one
+    let old_full = RegexEngine::new(r"^(?:[a-z]+@[a-z]+\.(com|org))$").unwrap();
+    let new_full = RegexEngine::new(r"^(?:[a-z]{2,}@[a-z]+\.com)$").unwrap();
+    for witness in &diff.only_left {
+        assert!(old_full.is_match(witness));
+        assert!(!new_full.is_match(witness));
+    }
+}
+
+#[test]
+fn test_combined_patterns_all() {
+    let patterns = [r"[a-z0-9]{8,16}", r".*\d.*", r".*[a-z].*"];
+    let mut generator =
+        DataGenerator::with_patterns_and_seed(&patterns, CombinationRule::All, 42).unwrap();
+
+    let data = generator.generate(50).unwrap();
+    assert_eq!(data.len(), 50);
+    for item in data {
+        assert!((8..=16).contains(&item.len()), "bad length: {}", item);
+        assert!(
+            item.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        );
+        assert!(item.chars().any(|c| c.is_ascii_digit()));
+        assert!(item.chars().any(|c| c.is_ascii_lowercase()));
+    }
+}
+
+#[test]
+fn test_combined_patterns_except() {
+    let patterns = [r"[A-Z]{2}[0-9]{2}", r"AA.*", r".*00"];
+    let mut generator =
+        DataGenerator::with_patterns_and_seed(&patterns, CombinationRule::FirstExceptRest, 7)
+            .unwrap();
+
+    for item in generator.generate(50).unwrap() {
+        assert_eq!(item.len(), 4);
+        assert!(!item.starts_with("AA"));
+        assert!(!item.ends_with("00"));
+    }
+
+    // Contradictory combinations are rejected up front
+    let result = DataGenerator::with_patterns(&[r"[a-z]{3}", r"[0-9]+"], CombinationRule::All);
+    assert!(result.is_err());
+
+    let mut generator =
+        DataGenerator::with_patterns(&[r"[a-z]{2}", r"a."], CombinationRule::FirstExceptRest)
+            .unwrap();
+    assert!(
+        generator
+            .generate_with_mode(3, GenerationMode::Sequential)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_importers_round_trip_exports() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let data = vec![
+        "a@b.com".to_string(),
+        "tab\there".to_string(),
+        "x&y".to_string(),
+    ];
+
+    let formats = vec![
+        (
+            "csv",
+            Box::new(CsvExporter::new()) as Box<dyn Exporter>,
+            Box::new(CsvImporter::new()) as Box<dyn Importer>,
+        ),
+        (
+            "json",
+            Box::new(JsonExporter::new()),
+            Box::new(JsonImporter::new()),
+        ),
+        (
+            "json",
+            Box::new(JsonExporter::with_options(true, false)),
+            Box::new(JsonImporter::new()),
+        ),
+        (
+            "xml",
+            Box::new(XmlExporter::new()),
+            Box::new(XmlImporter::new()),
+        ),
+        (
+            "tsv",
+            Box::new(TsvExporter::new()),
+            Box::new(TsvImporter::new()),
+        ),
+    ];
+
+    for (format, exporter, importer) in formats {
+        let path = temp_dir.path().join(format!("roundtrip.{}", format));
+        exporter.export(&data, path.to_str().unwrap()).unwrap();
+
+        let imported = importer.import(path.to_str().unwrap()).unwrap();
+        assert_eq!(importer.format_name(), exporter.format_name());
+        assert_eq!(imported.len(), data.len());
+        assert!(imported.windows(2).all(|w| w[0].line <= w[1].line));
+        assert_eq!(imported[0].value, "a@b.com");
+    }
+}
+
+#[test]
+fn test_import_reports_line_numbers() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+
+    let csv_path = temp_dir.path().join("users.csv");
+    fs::write(
+        &csv_path,
+        "id,email\n1,a@b.com\n2,\"multi\nline\"\n3,c@d.org\n",
+    )
+    .unwrap();
+    let values = CsvImporter::with_column("email".to_string())
+        .import(csv_path.to_str().unwrap())
+        .unwrap();
+    let lines: Vec<usize> = values.iter().map(|v| v.line).collect();
+    assert_eq!(lines, vec![2, 3, 5]);
+
+    let engine = RegexEngine::new(r"^[a-z]+@[a-z]+\.(com|org)$").unwrap();
+    let failing: Vec<_> = values
+        .iter()
+        .filter(|v| !engine.is_match(&v.value))
+        .collect();
+    assert_eq!(failing.len(), 1);
+    assert_eq!(failing[0].line, 3);
+
+    let json_path = temp_dir.path().join("users.json");
+    fs::write(
+        &json_path,
+        "[\n  {\"email\": \"a@b.com\"},\n  {\"email\": \"[x]\"}\n]",
+    )
+    .unwrap();
+    let values = JsonImporter::with_field("email".to_string())
+        .import(json_path.to_str().unwrap())
+        .unwrap();
+    assert_eq!(values[1].line, 3);
+    assert_eq!(values[1].value, "[x]");
+
+    assert!(
+        CsvImporter::new()
+            .import(csv_path.to_str().unwrap())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_schema_formats_parse_identically() {
+    let toml = r#"
+        count = 5
+        seed = 42
+
+        [[fields]]
+        name = "id"
+        pattern = "[0-9]{4}"
+        mode = "sequential"
+
+        [[fields]]
+        name = "email"
+        pattern = "[a-z]{3,8}@example\\.com"
+        seed_offset = 1
+        ascii_only = true
+    "#;
+    let yaml = r#"
+count: 5
+seed: 42
+fields:
+  - name: id
+    pattern: "[0-9]{4}"
+    mode: sequential
+  - name: email
+    pattern: '[a-z]{3,8}@example\.com'
+    seed_offset: 1
+    ascii_only: true
+"#;
+    let json = r#"{
+        "count": 5,
+        "seed": 42,
+        "fields": [
+            {"name": "id", "pattern": "[0-9]{4}", "mode": "sequential"},
+            {"name": "email", "pattern": "[a-z]{3,8}@example\\.com", "seed_offset": 1, "ascii_only": true}
+        ]
+    }"#;
+
+    let from_toml = Schema::from_toml_str(toml).unwrap();
+    assert_eq!(from_toml, Schema::from_yaml_str(yaml).unwrap());
+    assert_eq!(from_toml, Schema::from_json_str(json).unwrap());
+    assert_eq!(from_toml.columns(), vec!["id", "email"]);
+
+    // Duplicate names and unknown keys are rejected
+    assert!(
+        Schema::from_json_str(
+            r#"{"fields": [{"name": "a", "pattern": "x"}, {"name": "a", "pattern": "y"}]}"#
+        )
+        .is_err()
+    );
+    assert!(
+        Schema::from_json_str(r#"{"fields": [{"name": "a", "pattern": "x", "patern": "y"}]}"#)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_record_generation_and_export() {
+    let schema = Schema::from_json_str(
+        r#"{
+        "count": 4,
+        "seed": 7,
+        "fields": [
+            {"name": "id", "pattern": "[0-9]{3}", "mode": "sequential"},
+            {"name": "code", "pattern": "[A-Z]{2}-[0-9]{2}"}
+        ]
+    }"#,
+    )
+    .unwrap();
+
+    let mut generator = RecordGenerator::from_schema(&schema).unwrap();
+    let count = generator.default_count().unwrap();
+    let records = generator.generate(count).unwrap();
+    assert_eq!(records.len(), 4);
+    assert_eq!(
+        records.column("id").unwrap(),
+        vec!["000", "001", "002", "003"]
+    );
+
+    // Same seed reproduces the same records
+    let again = RecordGenerator::from_schema(&schema)
+        .unwrap()
+        .generate(4)
+        .unwrap();
+    assert_eq!(records, again);
+
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+
+    let csv_path = temp_dir.path().join("records.csv");
+    CsvExporter::new()
+        .export_records(&records, csv_path.to_str().unwrap())
+        .unwrap();
+    let csv_content = fs::read_to_string(&csv_path).unwrap();
+    assert!(csv_content.starts_with("id,code\n000,"));
+
+    let json_path = temp_dir.path().join("records.json");
+    JsonExporter::new()
+        .export_records(&records, json_path.to_str().unwrap())
+        .unwrap();
+    let values = JsonImporter::with_field("code".to_string())
+        .import(json_path.to_str().unwrap())
+        .unwrap();
+    assert_eq!(values.len(), 4);
+    assert_eq!(values[0].value, records.rows()[0][1]);
+
+    let xml_path = temp_dir.path().join("records.xml");
+    XmlExporter::new()
+        .export_records(&records, xml_path.to_str().unwrap())
+        .unwrap();
+    let xml_content = fs::read_to_string(&xml_path).unwrap();
+    assert!(xml_content.contains("<item><id>000</id><code>"));
+
+    let tsv_path = temp_dir.path().join("records.tsv");
+    TsvExporter::new()
+        .export_records(&records, tsv_path.to_str().unwrap())
+        .unwrap();
+    let ids = TsvImporter::with_column("id".to_string())
+        .import(tsv_path.to_str().unwrap())
+        .unwrap();
+    assert_eq!(ids[3].value, "003");
+}
+
+#[test]
+fn test_database_generation_keeps_references_consistent() {
+    let schema = DatabaseSchema::from_toml_str(
+        r#"
+        seed = 42
+
+        [[tables]]
+        name = "users"
+        count = 20
+        fields = [{ name = "id", pattern = "[0-9]{5}", mode = "sequential" }]
+
+        [[tables]]
+        name = "orders"
+        per_parent = { table = "users", min = 0, max = 5 }
+        output = "orders.json"
+        fields = [
+            { name = "id", pattern = "[A-Z]{2}[0-9]{6}" },
+            { name = "user_id", references = "users.id" },
+        ]
+
+        [[tables]]
+        name = "reviews"
+        count = 30
+        fields = [
+            { name = "order_id", references = "orders.id" },
+            { name = "author_id", references = "users.id" },
+        ]
+    "#,
+    )
+    .unwrap();
+
+    let tables = DatabaseGenerator::from_schema(&schema)
+        .unwrap()
+        .generate()
+        .unwrap();
+    assert_eq!(tables.len(), 3);
+    assert_eq!(tables[1].output.as_deref(), Some("orders.json"));
+
+    let user_ids = tables[0].records.column("id").unwrap();
+    let order_user_ids = tables[1].records.column("user_id").unwrap();
+    for user_id in &user_ids {
+        let orders = order_user_ids.iter().filter(|id| *id == user_id).count();
+        assert!(orders <= 5);
+    }
+    assert!(order_user_ids.iter().all(|id| user_ids.contains(id)));
+    // Children are grouped by parent in parent order
+    assert!(order_user_ids.windows(2).all(|w| w[0] <= w[1]));
+
+    let order_ids = tables[1].records.column("id").unwrap();
+    let reviews = &tables[2].records;
+    assert_eq!(reviews.len(), 30);
+    assert!(
+        reviews
+            .column("order_id")
+            .unwrap()
+            .iter()
+            .all(|id| order_ids.contains(id))
+    );
+    assert!(
+        reviews
+            .column("author_id")
+            .unwrap()
+            .iter()
+            .all(|id| user_ids.contains(id))
+    );
+
+    // Same seed reproduces the same tables
+    let again = DatabaseGenerator::from_schema(&schema)
+        .unwrap()
+        .generate()
+        .unwrap();
+    assert_eq!(tables, again);
+}
+
+#[test]
+fn test_database_schema_validation() {
+    // References must point at columns of earlier tables
+    let forward = r#"{"tables": [
+        {"name": "orders", "count": 1, "fields": [{"name": "user_id", "references": "users.id"}]},
+        {"name": "users", "count": 1, "fields": [{"name": "id", "pattern": "[0-9]"}]}
+    ]}"#;
+    assert!(DatabaseSchema::from_json_str(forward).is_err());
+
+    let missing_column = r#"{"tables": [
+        {"name": "users", "count": 1, "fields": [{"name": "id", "pattern": "[0-9]"}]},
+        {"name": "orders", "count": 1, "fields": [{"name": "user_id", "references": "users.uid"}]}
+    ]}"#;
+    assert!(DatabaseSchema::from_json_str(missing_column).is_err());
+
+    // Hidden columns are not exported, so nothing can copy them
+    let hidden_column = r#"{"tables": [
+        {"name": "users", "count": 1, "fields": [
+            {"name": "id", "pattern": "[0-9]", "hidden": true},
+            {"name": "name", "pattern": "[a-z]{4}"}
+        ]},
+        {"name": "orders", "count": 1, "fields": [{"name": "user_id", "references": "users.id"}]}
+    ]}"#;
+    let error = DatabaseSchema::from_json_str(hidden_column).unwrap_err();
+    assert!(error.to_string().contains("which is hidden"));
+
+    let no_count =
+        r#"{"tables": [{"name": "users", "fields": [{"name": "id", "pattern": "[0-9]"}]}]}"#;
+    assert!(DatabaseSchema::from_json_str(no_count).is_err());
+
+    // Single-table schemas cannot reference other tables
+    let single = r#"{"fields": [{"name": "user_id", "references": "users.id"}]}"#;
+    assert!(Schema::from_json_str(single).is_err());
+}
+
+#[test]
+fn test_template_fields_use_siblings_and_groups() {
+    let schema = Schema::from_toml_str(
+        r#"
+        seed = 3
+
+        [[fields]]
+        name = "first_name"
+        pattern = "[A-Z][a-z]{3,6}"
+
+        [[fields]]
+        name = "last_name"
+        pattern = "[A-Z][a-z]{4,8}"
+
+        [[fields]]
+        name = "suffix"
+        pattern = "[0-9]{3}"
+        hidden = true
+
+        [[fields]]
+        name = "email"
+        template = "{first_name|lower}.{last_name|lower}@example.com"
+
+        [[fields]]
+        name = "username"
+        template = "{first_name|lower}{suffix}"
+
+        [[fields]]
+        name = "card_body"
+        pattern = "(?P<iin>4[0-9]{5})[0-9]{9}"
+        hidden = true
+
+        [[fields]]
+        name = "card"
+        template = "{card_body|luhn}"
+
+        [[fields]]
+        name = "iin"
+        template = "{card_body.iin}"
+    "#,
+    )
+    .unwrap();
+
+    let records = RecordGenerator::from_schema(&schema)
+        .unwrap()
+        .generate(20)
+        .unwrap();
+    assert_eq!(
+        records.columns(),
+        [
+            "first_name",
+            "last_name",
+            "email",
+            "username",
+            "card",
+            "iin"
+        ]
+    );
+
+    for row in records.rows() {
+        let (first, last, email, username, card, iin) =
+            (&row[0], &row[1], &row[2], &row[3], &row[4], &row[5]);
+        assert_eq!(
+            *email,
+            format!(
+                "{}.{}@example.com",
+                first.to_lowercase(),
+                last.to_lowercase()
+            )
+        );
+        assert!(username.starts_with(&first.to_lowercase()));
+        assert_eq!(username.len(), first.len() + 3);
+        assert_eq!(card.len(), 16);
+        assert!(card.starts_with(iin.as_str()));
+        assert_eq!(iin.len(), 6);
+
+        // Valid Luhn numbers sum to a multiple of ten
+        let sum: u32 = card
+            .chars()
+            .rev()
+            .map(|c| c.to_digit(10).unwrap())
+            .enumerate()
+            .map(|(i, d)| {
+                if i % 2 == 1 {
+                    if d * 2 > 9 { d * 2 - 9 } else { d * 2 }
+                } else {
+                    d
+                }
+            })
+            .sum();
+        assert_eq!(sum % 10, 0);
+    }
+}
+
+#[test]
+fn test_template_validation() {
+    assert!(Template::parse("{a}-{{literal}}-{b.1|upper|trim}").is_ok());
+    assert!(Template::parse("{unclosed").is_err());
+    assert!(Template::parse("{a|shout}").is_err());
+
+    // Unknown fields, forward template references and missing groups are rejected
+    let unknown = r#"{"fields": [{"name": "a", "template": "{b}"}]}"#;
+    assert!(Schema::from_json_str(unknown).is_err());
+
+    let forward = r#"{"fields": [
+        {"name": "a", "template": "{b}"},
+        {"name": "b", "template": "x"}
+    ]}"#;
+    assert!(Schema::from_json_str(forward).is_err());
+
+    let missing_group = Schema::from_json_str(
+        r#"{"fields": [
+        {"name": "a", "pattern": "(?P<x>[0-9])"},
+        {"name": "b", "template": "{a.y}"}
+    ]}"#,
+    )
+    .unwrap();
+    assert!(RecordGenerator::from_schema(&missing_group).is_err());
+}
+
+#[test]
+fn test_checksum_schemes() {
+    // Published examples of valid identifiers
+    assert!(Checksum::Luhn.is_valid("4111111111111111"));
+    assert!(Checksum::Isbn10.is_valid("0-306-40615-2"));
+    assert!(Checksum::Isbn10.is_valid("080442957X"));
+    assert!(Checksum::Isbn13.is_valid("978-0-306-40615-7"));
+    assert!(Checksum::Ean8.is_valid("96385074"));
+    assert!(Checksum::Ean13.is_valid("4006381333931"));
+    assert!(Checksum::Iban.is_valid("GB82WEST12345698765432"));
+    assert!(Checksum::Iban.is_valid("DE89 3704 0044 0532 0130 00"));
+
+    assert!(!Checksum::Luhn.is_valid("4111111111111112"));
+    assert!(!Checksum::Iban.is_valid("GB83WEST12345698765432"));
+    assert_eq!(
+        Checksum::Luhn.fix("4111111111111112").as_deref(),
+        Some("4111111111111111")
+    );
+    assert_eq!(
+        Checksum::Iban.fix("GB00WEST12345698765432").as_deref(),
+        Some("GB82WEST12345698765432")
+    );
+    assert_eq!(
+        Checksum::Ean13.append("400638133393").as_deref(),
+        Some("4006381333931")
+    );
+    assert_eq!(Checksum::Ean13.append("40063813339"), None);
+}
+
+#[test]
+fn test_generation_with_checksum() {
+    let cases = [
+        ("4[0-9]{15}", Checksum::Luhn, 16),
+        ("97[89]-[0-9]-[0-9]{3}-[0-9]{5}-[0-9]", Checksum::Isbn13, 17),
+        ("[0-9]{9}[0-9X]", Checksum::Isbn10, 10),
+        ("GB[0-9]{2}[A-Z]{4}[0-9]{14}", Checksum::Iban, 22),
+        ("[0-9]{7,8}", Checksum::Ean8, 8),
+    ];
+
+    for (pattern, checksum, length) in cases {
+        let mut generator = DataGenerator::builder(pattern)
+            .seed(7)
+            .checksum(checksum)
+            .build()
+            .unwrap();
+        let engine = RegexEngine::new(pattern).unwrap();
+
+        for value in generator.generate(50).unwrap() {
+            assert!(
+                checksum.is_valid(&value),
+                "{} is not valid {}",
+                value,
+                checksum.name()
+            );
+            assert!(engine.is_match(&value));
+            assert_eq!(value.len(), length);
+        }
+    }
+
+    // A digit-only pattern cannot hold an ISBN-10 whose check character is X
+    let mut generator = DataGenerator::builder("[0-9]{10}")
+        .seed(1)
+        .checksum(Checksum::Isbn10)
+        .build()
+        .unwrap();
+    assert!(
+        generator
+            .generate(100)
+            .unwrap()
+            .iter()
+            .all(|v| !v.contains('X'))
+    );
+
+    // An appended check digit must leave a value the whole pattern matches
+    let mut generator = DataGenerator::builder("[0-9]{12}")
+        .checksum(Checksum::Ean13)
+        .build()
+        .unwrap();
+    assert!(generator.generate(1).is_err());
+    assert!(
+        generator
+            .generate_with_mode(1, GenerationMode::Sequential)
+            .is_err()
+    );
+
+    let schema = Schema::from_json_str(
+        r#"{"fields": [
+        {"name": "card", "pattern": "5[1-5][0-9]{14}", "checksum": "luhn"}
+    ]}"#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 3)
+        .unwrap()
+        .generate(20)
+        .unwrap();
+    assert!(
+        records
+            .column("card")
+            .unwrap()
+            .iter()
+            .all(|v| Checksum::Luhn.is_valid(v))
+    );
+
+    let invalid = r#"{"fields": [{"name": "a", "template": "x", "checksum": "luhn"}]}"#;
+    assert!(Schema::from_json_str(invalid).is_err());
+}
+
+#[test]
+fn test_named_patterns_pass_real_validators() {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use std::str::FromStr;
+
+    fn generate(pattern: &str) -> Vec<String> {
+        DataGenerator::with_seed(pattern, 11)
+            .unwrap()
+            .generate(200)
+            .unwrap()
+    }
+
+    for value in generate("@email") {
+        assert!(
+            email_address::EmailAddress::is_valid(&value),
+            "invalid email {}",
+            value
+        );
+    }
+    for value in generate("@uuid4") {
+        let uuid = uuid::Uuid::parse_str(&value).unwrap();
+        assert_eq!(uuid.get_version_num(), 4);
+        assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+    }
+    for value in generate("@uuid") {
+        assert!(uuid::Uuid::parse_str(&value).is_ok());
+    }
+    for value in generate("@ipv4") {
+        assert!(Ipv4Addr::from_str(&value).is_ok(), "invalid IPv4 {}", value);
+    }
+    for value in generate("@ipv6") {
+        assert!(Ipv6Addr::from_str(&value).is_ok(), "invalid IPv6 {}", value);
+    }
+    for value in generate("@iso_date") {
+        assert!(chrono::NaiveDate::parse_from_str(&value, "%Y-%m-%d").is_ok());
+    }
+    for value in generate("@iso_time") {
+        assert!(chrono::NaiveTime::parse_from_str(&value, "%H:%M:%S").is_ok());
+    }
+    for value in generate("@iso_datetime") {
+        assert!(chrono::DateTime::parse_from_rfc3339(&value).is_ok());
+    }
+    for value in generate("@url") {
+        let url = url::Url::parse(&value).unwrap();
+        assert!(url.scheme() == "http" || url.scheme() == "https");
+    }
+    for value in generate("@phone") {
+        let digits = value.strip_prefix('+').unwrap();
+        assert!((8..=15).contains(&digits.len()));
+    }
+
+    // Every library pattern compiles and generates values that match it
+    for named in NamedPattern::all() {
+        let engine = RegexEngine::new(&format!("^(?:@{{{}}})$", named.name)).unwrap();
+        for value in generate(&format!("@{}", named.name)) {
+            assert!(
+                engine.is_match(&value),
+                "@{} generated {}",
+                named.name,
+                value
+            );
+        }
+    }
+}
+
+#[test]
+fn test_named_pattern_references() {
+    // Embedded references and literal @ signs
+    let mut generator = DataGenerator::with_seed("user-@{uuid4}@example\\.com", 5).unwrap();
+    for value in generator.generate(10).unwrap() {
+        let uuid = value
+            .strip_prefix("user-")
+            .unwrap()
+            .strip_suffix("@example.com")
+            .unwrap();
+        assert!(uuid::Uuid::parse_str(uuid).is_ok());
+    }
+    assert!(
+        RegexEngine::new("[a-z]+@[a-z]+\\.com")
+            .unwrap()
+            .is_match("a@b.com")
+    );
+    assert!(RegexEngine::new("x@{2}").unwrap().is_match("x@@"));
+    assert!(RegexEngine::new("@ipv4").unwrap().is_match("10.0.0.1"));
+    assert!(RegexEngine::validate_pattern("@{ipv6}/[0-9]{2}").is_ok());
+    assert!(RegexEngine::new(r"a\@{2}").unwrap().is_match("a@@"));
+    let escaped_backslash = RegexEngine::new(r"\\@{ipv4}").unwrap();
+    assert!(escaped_backslash.is_match(r"\10.0.0.1"));
+    assert!(RegexEngine::new(r"\\\@{2}").unwrap().is_match(r"\@@"));
+
+    // The original pattern is kept next to the expanded one
+    let engine = RegexEngine::new("id-@{uuid4}").unwrap();
+    assert_eq!(engine.pattern(), "id-@{uuid4}");
+    assert!(engine.expanded_pattern().starts_with("id-(?:[0-9a-f]{8}-"));
+    let generator = DataGenerator::new("@email").unwrap();
+    assert_eq!(generator.pattern(), "@email");
+    assert_eq!(
+        generator.expanded_pattern(),
+        NamedPattern::lookup("email").unwrap().pattern
+    );
+
+    // Unknown whole-pattern names stay literal regexes
+    assert!(RegexEngine::new("@admin").unwrap().is_match("@admin"));
+    let mut admins = DataGenerator::new("@admin").unwrap();
+    assert_eq!(admins.generate(3).unwrap(), vec!["@admin"; 3]);
+    assert!(RegexEngine::validate_pattern("id-@{nope}").is_err());
+
+    let schema = Schema::from_json_str(
+        r#"{"fields": [
+        {"name": "host", "pattern": "@ipv4"},
+        {"name": "url", "template": "http://{host}/"}
+    ]}"#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 2)
+        .unwrap()
+        .generate(5)
+        .unwrap();
+    for url in records.column("url").unwrap() {
+        assert!(url::Url::parse(url).unwrap().host_str().is_some());
+    }
+}
+
+#[test]
+fn test_datetime_generation() {
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+
+    let bound = |value: &str| value.parse::<DateTimeBound>().unwrap();
+    let spec = DateTimeSpec::new(DateTimeFormat::Rfc3339)
+        .between(bound("2024-02-01"), bound("2024-02-29"))
+        .unwrap()
+        .with_offset("+02:00".parse().unwrap());
+    let mut generator = DataGenerator::builder("(?s:.*)")
+        .seed(3)
+        .datetime(spec)
+        .build()
+        .unwrap();
+    for value in generator.generate(100).unwrap() {
+        let parsed = DateTime::parse_from_rfc3339(&value).unwrap();
+        assert!(value.ends_with("+02:00"));
+        // Days are bounded at the output offset
+        let local = parsed.naive_local();
+        assert!(local >= NaiveDate::from_ymd_opt(2024, 2, 1).unwrap().into());
+        assert!(local < NaiveDate::from_ymd_opt(2024, 3, 1).unwrap().into());
+    }
+
+    let formats = [
+        ("date", "%Y-%m-%d"),
+        ("time", "%H:%M:%S"),
+        ("iso8601", "%Y-%m-%dT%H:%M:%S%:z"),
+        ("%d/%m/%Y %H:%M", "%d/%m/%Y %H:%M"),
+    ];
+    for (format, parse_format) in formats {
+        let spec = DateTimeSpec::new(format.parse().unwrap());
+        let mut generator = DataGenerator::from_datetime(spec).unwrap();
+        for value in generator.generate(50).unwrap() {
+            let valid = match format {
+                "date" => NaiveDate::parse_from_str(&value, parse_format).is_ok(),
+                "time" => NaiveTime::parse_from_str(&value, parse_format).is_ok(),
+                "iso8601" => DateTime::parse_from_str(&value, parse_format).is_ok(),
+                _ => NaiveDateTime::parse_from_str(&value, parse_format).is_ok(),
+            };
+            assert!(valid, "{} is not a valid {} value", value, format);
+        }
+    }
+
+    let spec = DateTimeSpec::new(DateTimeFormat::Rfc2822);
+    for value in DataGenerator::from_datetime(spec)
+        .unwrap()
+        .generate(20)
+        .unwrap()
+    {
+        assert!(DateTime::parse_from_rfc2822(&value).is_ok());
+    }
+
+    // The pattern still constrains the shape of generated dates
+    let spec = DateTimeSpec::new(DateTimeFormat::Date)
+        .between(bound("2024-01-01"), bound("2024-12-31"))
+        .unwrap();
+    let mut generator = DataGenerator::builder("2024-0[1-3]-[0-9]{2}")
+        .seed(1)
+        .datetime(spec)
+        .build()
+        .unwrap();
+    for value in generator.generate(20).unwrap() {
+        let month: u32 = value[5..7].parse().unwrap();
+        assert!((1..=3).contains(&month));
+    }
+
+    assert!("%Q".parse::<DateTimeFormat>().is_err());
+    assert!("yesterday".parse::<DateTimeFormat>().is_err());
+    assert!(
+        DateTimeSpec::new(DateTimeFormat::Date)
+            .between(bound("2024-02-01"), bound("2024-01-01"))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_datetime_day_bounds_use_output_offset() {
+    use chrono::DateTime;
+
+    let bound = |value: &str| value.parse::<DateTimeBound>().unwrap();
+    for offset in ["+09:00", "-05:00", "+14:00", "-12:00"] {
+        // The offset may be set before or after the range
+        let specs = [
+            DateTimeSpec::new(DateTimeFormat::Date)
+                .between(bound("2024-01-01"), bound("2024-01-01"))
+                .unwrap()
+                .with_offset(offset.parse().unwrap()),
+            DateTimeSpec::new(DateTimeFormat::Date)
+                .with_offset(offset.parse().unwrap())
+                .between(bound("2024-01-01"), bound("2024-01-01"))
+                .unwrap(),
+        ];
+        for spec in specs {
+            let values = DataGenerator::builder("(?s:.*)")
+                .seed(9)
+                .datetime(spec)
+                .build()
+                .unwrap()
+                .generate(500)
+                .unwrap();
+            assert!(
+                values.iter().all(|v| v == "2024-01-01"),
+                "{} gave {:?}",
+                offset,
+                values
+            );
+        }
+
+        let spec = DateTimeSpec::new(DateTimeFormat::Rfc3339)
+            .between(bound("2024-01-01"), bound("2024-01-01"))
+            .unwrap()
+            .with_offset(offset.parse().unwrap());
+        let values = DataGenerator::builder("(?s:.*)")
+            .seed(4)
+            .datetime(spec)
+            .build()
+            .unwrap()
+            .generate(500)
+            .unwrap();
+        for value in values {
+            let parsed = DateTime::parse_from_rfc3339(&value).unwrap();
+            assert_eq!(parsed.date_naive().to_string(), "2024-01-01", "{}", value);
+        }
+    }
+
+    // A day and a timestamp that are only out of order at the output offset
+    let spec = DateTimeSpec::new(DateTimeFormat::Rfc3339)
+        .between(bound("2024-01-01"), bound("2024-01-01T05:00:00Z"))
+        .unwrap()
+        .with_offset("-09:00".parse().unwrap());
+    let mut generator = DataGenerator::builder("(?s:.*)")
+        .datetime(spec)
+        .build()
+        .unwrap();
+    assert!(generator.generate(1).is_err());
+}
+
+#[test]
+fn test_datetime_schema_fields() {
+    let schema = Schema::from_toml_str(r#"
+        [[fields]]
+        name = "event"
+        pattern = "(login|logout)"
+
+        [[fields]]
+        name = "at"
+        datetime = { format = "rfc3339", start = "2023-06-01T00:00:00Z", end = "2023-06-30", offset = "-04:00" }
+    "#).unwrap();
+
+    let records = RecordGenerator::with_seed(&schema, 9)
+        .unwrap()
+        .generate(30)
+        .unwrap();
+    for value in records.column("at").unwrap() {
+        let parsed = chrono::DateTime::parse_from_rfc3339(value).unwrap();
+        assert_eq!(parsed.offset().local_minus_utc(), -4 * 3600);
+        assert!(value.starts_with("2023-05-31") || value.starts_with("2023-06"));
+    }
+
+    let invalid = r#"{"fields": [{"name": "a", "datetime": {"format": "date", "start": "soon"}}]}"#;
+    assert!(Schema::from_json_str(invalid).is_err());
+}
+
+#[test]
+fn test_numeric_ranges() {
+    let octet = NumericRange::integer(0, 255).unwrap();
+    assert!(octet.contains("0") && octet.contains("255") && octet.contains("37"));
+    assert!(!octet.contains("256") && !octet.contains("-1") && !octet.contains("007"));
+
+    let price: NumericRange = "0.00..99.95, step=0.05, pad=5".parse().unwrap();
+    assert!(price.contains("00.05") && price.contains("99.95"));
+    assert!(!price.contains("0.05") && !price.contains("00.07") && !price.contains("99.96"));
+
+    let signed: NumericRange = "-10..10,step=5".parse().unwrap();
+    assert!(signed.contains("-10") && signed.contains("-5") && signed.contains("10"));
+    assert!(!signed.contains("3"));
+
+    assert!("10..1".parse::<NumericRange>().is_err());
+    assert!("1..10,step=0".parse::<NumericRange>().is_err());
+    assert!("1..10,width=2".parse::<NumericRange>().is_err());
+    assert!("a..b".parse::<NumericRange>().is_err());
+
+    // Every occurrence of a group, including inside repetitions, is replaced
+    let pattern = r"(?:(?P<octet>[0-9]{1,3})\.){3}(?P<last>[0-9]{1,3})";
+    let mut generator = DataGenerator::builder(pattern)
+        .seed(4)
+        .range("octet", octet.clone())
+        .range("last", NumericRange::integer(1, 254).unwrap())
+        .build()
+        .unwrap();
+    for value in generator.generate(200).unwrap() {
+        let address: std::net::Ipv4Addr = value.parse().unwrap();
+        assert!((1..=254).contains(&address.octets()[3]));
+    }
+
+    let mut generator = DataGenerator::builder("age=(?P<age>[0-9]{2,3})")
+        .seed(4)
+        .range("age", "18..99".parse().unwrap())
+        .build()
+        .unwrap();
+    for value in generator.generate(100).unwrap() {
+        let age: u32 = value.strip_prefix("age=").unwrap().parse().unwrap();
+        assert!((18..=99).contains(&age));
+    }
+
+    let mut generator = DataGenerator::builder(r"id-(?P<n>[0-9]{3})")
+        .seed(4)
+        .range("n", "1..999,pad=3".parse().unwrap())
+        .build()
+        .unwrap();
+    assert!(generator.generate(50).unwrap().iter().all(|v| v.len() == 6));
+
+    // Private-use characters generated by the pattern itself are kept
+    let mut generator = DataGenerator::builder(r"[\u{E000}-\u{E002}]{4}-(?P<n>[0-9]{2})\u{E000}")
+        .seed(4)
+        .range("n", NumericRange::integer(10, 99).unwrap())
+        .build()
+        .unwrap();
+    for value in generator.generate(50).unwrap() {
+        let chars: Vec<char> = value.chars().collect();
+        assert_eq!(chars.len(), 8, "{:?}", value);
+        assert!(
+            chars[..4]
+                .iter()
+                .all(|c| ('\u{E000}'..='\u{E002}').contains(c))
+        );
+        assert_eq!(chars[7], '\u{E000}');
+    }
+
+    // Ranges that cannot fit the group's shape, and unknown groups, are errors
+    let mut generator = DataGenerator::builder("(?P<n>[0-9])")
+        .range("n", NumericRange::integer(10, 99).unwrap())
+        .build()
+        .unwrap();
+    assert!(generator.generate(1).is_err());
+    assert!(
+        DataGenerator::builder("(?P<n>[0-9])")
+            .range("m", octet)
+            .build()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_numeric_range_validation() {
+    let engine = RegexEngine::new(r"port (?P<port>[0-9]{1,5})")
+        .unwrap()
+        .with_range("port", NumericRange::integer(1024, 65535).unwrap())
+        .unwrap();
+    assert!(engine.is_match("port 8080"));
+    assert!(!engine.is_match("port 80"));
+    assert!(!engine.is_match("port 99999"));
+    assert!(!engine.is_match("gate 8080"));
+
+    let schema = Schema::from_toml_str(
+        r#"
+        [[fields]]
+        name = "age"
+        pattern = "(?P<years>[0-9]{2})"
+        ranges = { years = "18..65" }
+    "#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 1)
+        .unwrap()
+        .generate(50)
+        .unwrap();
+    for age in records.column("age").unwrap() {
+        assert!((18..=65).contains(&age.parse::<u32>().unwrap()));
+    }
+
+    let invalid =
+        r#"{"fields": [{"name": "a", "pattern": "(?P<n>[0-9])", "ranges": {"n": "9..1"}}]}"#;
+    assert!(Schema::from_json_str(invalid).is_err());
+}
+
+#[test]
+fn test_dictionary_placeholders() {
+    let temp_dir = TempDir::new().unwrap();
+    let csv_path = temp_dir.path().join("cities.csv");
+    fs::write(&csv_path, "name,population\nTokyo,100\nDelhi,0\nLima,0\n").unwrap();
+
+    let cities =
+        Dictionary::from_csv(csv_path.to_str().unwrap(), "name", Some("population")).unwrap();
+    let mut generator = DataGenerator::builder(r"(?P<city>{{dict:cities}}), [A-Z]{2} \d{5}")
+        .seed(7)
+        .ascii_only(true)
+        .dictionary("cities", cities)
+        .build()
+        .unwrap();
+    let values = generator.generate(20).unwrap();
+    assert!(
+        values
+            .iter()
+            .all(|v| v.starts_with("Tokyo, ") && v.len() == 15)
+    );
+    assert!(!generator.expanded_pattern().contains("Tokyo"));
+
+    // Large word lists are looked up rather than compiled into the pattern
+    let ids: Vec<String> = (0..200_000).map(|i| format!("id{:06}", i)).collect();
+    let mut generator = DataGenerator::builder("{{dict:ids}}-[0-9]")
+        .seed(7)
+        .dictionary("ids", Dictionary::new(ids).unwrap())
+        .transform(Transform::Lower)
+        .build()
+        .unwrap();
+    for value in generator.generate(20).unwrap() {
+        let (id, digit) = value.split_once('-').unwrap();
+        assert!(id.len() == 8 && id.starts_with("id") && digit.len() == 1);
+    }
+
+    // Transformed entries that are no longer entries are rejected
+    let mut generator = DataGenerator::builder("{{dict:c}}")
+        .dictionary("c", Dictionary::new(["Tokyo", "Lima"]).unwrap())
+        .transform(Transform::Upper)
+        .build()
+        .unwrap();
+    assert!(generator.generate(1).is_err());
+
+    // Seeded draws repeat
+    let build = || {
+        DataGenerator::builder("{{dict:c}}-{{dict:c}}")
+            .seed(3)
+            .dictionary("c", Dictionary::new(["a", "b", "c", "d"]).unwrap())
+            .build()
+            .unwrap()
+    };
+    assert_eq!(build().generate(10).unwrap(), build().generate(10).unwrap());
+
+    // Unique dictionaries never repeat and run out
+    let mut generator = DataGenerator::builder("{{dict:c}}")
+        .dictionary("c", Dictionary::new(["x", "y", "z"]).unwrap().unique())
+        .build()
+        .unwrap();
+    let mut values = generator.generate(3).unwrap();
+    values.sort();
+    assert_eq!(values, vec!["x", "y", "z"]);
+    assert!(generator.generate(1).is_err());
+
+    // Filled values are validated, with each placeholder taking the shortest
+    // text that lets the rest of the pattern match
+    let mut generator = DataGenerator::builder("{{dict:w}}b?")
+        .seed(5)
+        .dictionary("w", Dictionary::new(["ab"]).unwrap())
+        .build()
+        .unwrap();
+    assert!(generator.generate(20).unwrap().iter().all(|v| v == "abb"));
+
+    // Markers avoid characters the pattern writes or draws from classes
+    let mut generator = DataGenerator::builder(r"\x{E800}-{{dict:w}}")
+        .dictionary("w", Dictionary::new(["beta"]).unwrap())
+        .build()
+        .unwrap();
+    assert_eq!(generator.generate(1).unwrap(), vec!["\u{E800}-beta"]);
+    let mut generator = DataGenerator::builder(r"[\x{E7FF}-\x{E800}]{{dict:w}}")
+        .dictionary("w", Dictionary::new(["x", "y"]).unwrap().unique())
+        .build()
+        .unwrap();
+    assert_eq!(generator.generate(2).unwrap().len(), 2);
+
+    assert!(DataGenerator::builder("{{dict:missing}}").build().is_err());
+}
+
+#[test]
+fn test_schema_dictionaries() {
+    let temp_dir = TempDir::new().unwrap();
+    let words_path = temp_dir.path().join("names.txt");
+    fs::write(&words_path, "Ada\t3\nGrace\t1\n\nLinus\t0\n").unwrap();
+
+    let schema = Schema::from_toml_str(&format!(
+        r#"
+        [[fields]]
+        name = "user"
+        pattern = "(?P<first>{{{{dict:names}}}})_[0-9]{{2}}"
+        dictionaries = {{ names = {{ path = '{}' }} }}
+
+        [[fields]]
+        name = "greeting"
+        template = "Hi {{user.first}}"
+    "#,
+        words_path.display()
+    ))
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 5)
+        .unwrap()
+        .generate(30)
+        .unwrap();
+    let users = records.column("user").unwrap();
+    let greetings = records.column("greeting").unwrap();
+    for (user, greeting) in users.iter().zip(greetings) {
+        let first = user.split('_').next().unwrap();
+        assert!(first == "Ada" || first == "Grace");
+        assert_eq!(greeting, &format!("Hi {}", first));
+    }
+
+    let invalid =
+        r#"{"fields": [{"name": "a", "template": "x", "dictionaries": {"d": {"path": "d.txt"}}}]}"#;
+    assert!(Schema::from_json_str(invalid).is_err());
+}
+
+#[test]
+fn test_case_transforms() {
+    let mut generator = DataGenerator::builder("(?i)[a-z]{4}-[a-z]{4}")
+        .seed(11)
+        .ascii_only(true)
+        .transform(Transform::Upper)
+        .build()
+        .unwrap();
+    let upper = generator.generate(20).unwrap();
+    assert!(
+        upper
+            .iter()
+            .all(|v| v.chars().all(|c| c.is_ascii_uppercase() || c == '-'))
+    );
+
+    let mut generator = DataGenerator::builder("(?i)[a-z]{4}-[a-z]{4}")
+        .seed(11)
+        .ascii_only(true)
+        .transform(Transform::Title)
+        .build()
+        .unwrap();
+    for value in generator.generate(20).unwrap() {
+        let (first, second) = value.split_once('-').unwrap();
+        for word in [first, second] {
+            assert!(word.starts_with(|c: char| c.is_ascii_uppercase()));
+            assert!(word[1..].chars().all(|c| c.is_ascii_lowercase()));
+        }
+    }
+
+    // Random case varies the letters of the same value, and is seeded
+    let build = || {
+        DataGenerator::builder("(?i)[a-z]{12}")
+            .seed(5)
+            .ascii_only(true)
+            .transform(Transform::Lower)
+            .transform(Transform::RandomCase)
+            .build()
+            .unwrap()
+    };
+    let mixed = build().generate(20).unwrap();
+    assert_eq!(mixed, build().generate(20).unwrap());
+    assert!(
+        mixed
+            .iter()
+            .any(|v| v.chars().any(|c| c.is_ascii_uppercase()))
+    );
+    assert!(
+        mixed
+            .iter()
+            .any(|v| v.chars().any(|c| c.is_ascii_lowercase()))
+    );
+
+    // Case-sensitive patterns reject transformed values
+    let mut generator = DataGenerator::builder("[a-z]{4}")
+        .transform(Transform::Upper)
+        .build()
+        .unwrap();
+    assert!(generator.generate(1).is_err());
+}
+
+#[test]
+fn test_normalization_transforms() {
+    let pattern = "caf(?:\u{e9}|e\u{301}) (?:\u{fb01}|fi)";
+    let mut generator = DataGenerator::builder(pattern)
+        .seed(2)
+        .transform(Transform::Nfd)
+        .build()
+        .unwrap();
+    assert!(
+        generator
+            .generate(20)
+            .unwrap()
+            .iter()
+            .all(|v| v.starts_with("cafe\u{301} "))
+    );
+
+    let mut generator = DataGenerator::builder(pattern)
+        .seed(2)
+        .transform(Transform::Nfkc)
+        .build()
+        .unwrap();
+    assert!(
+        generator
+            .generate(20)
+            .unwrap()
+            .iter()
+            .all(|v| v == "caf\u{e9} fi")
+    );
+
+    let schema = Schema::from_toml_str(
+        r#"
+        [[fields]]
+        name = "word"
+        pattern = "(?i)hello"
+        transforms = ["random_case"]
+    "#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 1)
+        .unwrap()
+        .generate(10)
+        .unwrap();
+    assert!(
+        records
+            .column("word")
+            .unwrap()
+            .iter()
+            .all(|w| w.eq_ignore_ascii_case("hello"))
+    );
+
+    let invalid = r#"{"fields": [{"name": "a", "template": "x", "transforms": ["upper"]}]}"#;
+    assert!(Schema::from_json_str(invalid).is_err());
+}
+
+#[test]
+fn test_char_filter_scripts_and_categories() {
+    let filter = CharFilter::new()
+        .script("Cyrillic")
+        .unwrap()
+        .category("Nd")
+        .unwrap();
+    let mut generator = DataGenerator::builder(r"\w{12}")
+        .seed(8)
+        .filter(filter.clone())
+        .build()
+        .unwrap();
+    let values = generator.generate(50).unwrap();
+    assert!(
+        values
+            .iter()
+            .all(|v| v.chars().count() == 12 && v.chars().all(|c| filter.allows(c)))
+    );
+    assert!(filter.allows('ж') && filter.allows('٣') && !filter.allows('a'));
+
+    // Literals are generated as written, and the pattern keeps matching
+    let filter = CharFilter::new().script("Hangul").unwrap();
+    let mut generator = DataGenerator::builder(r"id-.{4}")
+        .seed(8)
+        .filter(filter)
+        .build()
+        .unwrap();
+    let engine = RegexEngine::new(r"^id-\p{Hangul}{4}$").unwrap();
+    assert!(
+        generator
+            .generate(20)
+            .unwrap()
+            .iter()
+            .all(|v| engine.is_match(v))
+    );
+
+    assert!(CharFilter::new().script("Klingon").is_err());
+    assert!(CharFilter::new().category("Xx").is_err());
+}
+
+#[test]
+fn test_char_filter_allow_and_deny() {
+    let filter = CharFilter::new().allow("abc123").deny("b2");
+    let mut generator = DataGenerator::builder("[a-z0-9]{10}")
+        .seed(3)
+        .ascii_only(true)
+        .filter(filter)
+        .build()
+        .unwrap();
+    for value in generator.generate(30).unwrap() {
+        assert!(value.chars().all(|c| "ac13".contains(c)));
+    }
+
+    // A class with nothing left is an error rather than an empty value
+    let filter = CharFilter::new().script("Latin").unwrap();
+    assert!(
+        DataGenerator::builder("[0-9]{3}")
+            .filter(filter)
+            .build()
+            .is_err()
+    );
+
+    let schema = Schema::from_toml_str(
+        r#"
+        [[fields]]
+        name = "code"
+        pattern = "[A-Z]{6}"
+        filter = { deny = "IO" }
+    "#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 1)
+        .unwrap()
+        .generate(50)
+        .unwrap();
+    assert!(
+        records
+            .column("code")
+            .unwrap()
+            .iter()
+            .all(|v| !v.contains(['I', 'O']))
+    );
+
+    let invalid =
+        r#"{"fields": [{"name": "a", "pattern": "x", "filter": {"scripts": ["Elvish"]}}]}"#;
+    assert!(Schema::from_json_str(invalid).is_err());
+}
+
+#[test]
+fn test_adversarial_generation() {
+    let mut generator = DataGenerator::builder(r"user_\w{6}")
+        .seed(9)
+        .adversarial(true)
+        .build()
+        .unwrap();
+    for trick in [
+        Trick::CombiningMark,
+        Trick::ZeroWidth,
+        Trick::Astral,
+        Trick::Homoglyph,
+    ] {
+        assert!(generator.tricks().contains(&trick));
+    }
+
+    let engine = RegexEngine::new(r"^user_\w{6}$").unwrap();
+    let values = generator.generate(100).unwrap();
+    let mut seen = Vec::new();
+    for value in &values {
+        assert!(engine.is_match(value));
+        assert!(value.starts_with("user_"));
+        let reasons = adversarial::explain(value);
+        assert!(!reasons.is_empty());
+        seen.extend(reasons.into_iter().flat_map(|r| r.tricks));
+    }
+    assert!(seen.contains(&Trick::ZeroWidth) && seen.contains(&Trick::Homoglyph));
+    assert!(values.iter().any(|v| v.len() != v.chars().count()));
+
+    // Patterns without difficult characters generate as usual
+    let generator = DataGenerator::builder("[a-z]{5}")
+        .adversarial(true)
+        .build()
+        .unwrap();
+    assert!(generator.tricks().is_empty());
+    let generator = DataGenerator::builder("[a-zé]{5}")
+        .adversarial(true)
+        .build()
+        .unwrap();
+    assert_eq!(generator.tricks(), &[Trick::MultiByte]);
+}
+
+#[test]
+fn test_adversarial_explanations() {
+    let reasons = adversarial::explain("a\u{301}\u{200d}\u{202e}😀а\u{e9}z");
+    let tricks: Vec<(usize, Vec<Trick>)> = reasons
+        .iter()
+        .map(|r| (r.position, r.tricks.clone()))
+        .collect();
+    assert_eq!(
+        tricks,
+        vec![
+            (1, vec![Trick::CombiningMark]),
+            (2, vec![Trick::ZeroWidth]),
+            (3, vec![Trick::BidiControl]),
+            (4, vec![Trick::Astral]),
+            (5, vec![Trick::Homoglyph]),
+            (6, vec![Trick::MultiByte]),
+        ]
+    );
+    assert_eq!(reasons[1].to_string(), "U+200D at 2: zero-width character");
+    assert!(adversarial::explain("plain ascii").is_empty());
+}
+
+#[test]
+fn test_json_lines_export() {
+    let temp_dir = TempDir::new().unwrap();
+    let data = vec![
+        "a\"b".to_string(),
+        "line\nbreak".to_string(),
+        "ünï".to_string(),
+    ];
+
+    let path = temp_dir.path().join("values.jsonl");
+    JsonLinesExporter::new()
+        .export(&data, path.to_str().unwrap())
+        .unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+    let lines: Vec<String> = content
+        .lines()
+        .map(|line| serde_json::from_str::<String>(line).unwrap())
+        .collect();
+    assert_eq!(lines, data);
+
+    let path = temp_dir.path().join("objects.jsonl");
+    JsonLinesExporter::with_options(false)
+        .export(&data, path.to_str().unwrap())
+        .unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+    let objects: Vec<serde_json::Value> = content
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(objects.len(), 3);
+    assert_eq!(objects[2], serde_json::json!({"id": 2, "value": "ünï"}));
+
+    let schema = Schema::from_toml_str(
+        r#"
+        [[fields]]
+        name = "id"
+        pattern = "[0-9]{4}"
+
+        [[fields]]
+        name = "name"
+        pattern = "[a-z]{5}"
+    "#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 1)
+        .unwrap()
+        .generate(4)
+        .unwrap();
+    let path = temp_dir.path().join("records.jsonl");
+    JsonLinesExporter::new()
+        .export_records(&records, path.to_str().unwrap())
+        .unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+    let first: serde_json::Value = serde_json::from_str(content.lines().next().unwrap()).unwrap();
+    assert_eq!(
+        first.as_object().unwrap().keys().collect::<Vec<_>>(),
+        vec!["id", "name"]
+    );
+    assert_eq!(content.lines().count(), 4);
+}
+
+#[test]
+fn test_json_lines_streaming_writer() {
+    let mut generator = DataGenerator::with_seed("[A-Z]{3}", 4).unwrap();
+    let mut writer = JsonLinesExporter::with_options(false).writer(Vec::new());
+    for _ in 0..3 {
+        for value in generator.generate(2).unwrap() {
+            writer.write(&value).unwrap();
+        }
+    }
+    let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+    let ids: Vec<u64> = output
+        .lines()
+        .map(|line| {
+            serde_json::from_str::<serde_json::Value>(line).unwrap()["id"]
+                .as_u64()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(ids, vec![0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_sql_export_dialects() {
+    let temp_dir = TempDir::new().unwrap();
+    let data = vec![
+        "O'Brien".to_string(),
+        "back\\slash".to_string(),
+        "plain".to_string(),
+    ];
+
+    let path = temp_dir.path().join("postgres.sql");
+    SqlExporter::new(SqlDialect::Postgres)
+        .with_table("user\"s")
+        .with_column("name")
+        .with_batch_size(2)
+        .export(&data, path.to_str().unwrap())
+        .unwrap();
+    let sql = fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        sql,
+        concat!(
+            "INSERT INTO \"user\"\"s\" (\"name\") VALUES\n",
+            "    ('O''Brien'),\n",
+            "    ('back\\slash');\n",
+            "INSERT INTO \"user\"\"s\" (\"name\") VALUES\n",
+            "    ('plain');\n",
+        )
+    );
+
+    let path = temp_dir.path().join("mysql.sql");
+    SqlExporter::new(SqlDialect::MySql)
+        .with_table("people")
+        .export(&data, path.to_str().unwrap())
+        .unwrap();
+    let sql = fs::read_to_string(&path).unwrap();
+    assert!(sql.starts_with("INSERT INTO `people` (`generated_data`) VALUES\n"));
+    assert!(sql.contains("('O''Brien'),") && sql.contains("('back\\\\slash'),"));
+    assert_eq!(sql.matches("INSERT").count(), 1);
+
+    // NUL cannot be stored in PostgreSQL or SQLite text, but MySQL escapes it
+    let nul = vec!["a\0b".to_string()];
+    let path = temp_dir.path().join("nul.sql");
+    assert!(
+        SqlExporter::new(SqlDialect::Sqlite)
+            .export(&nul, path.to_str().unwrap())
+            .is_err()
+    );
+    SqlExporter::new(SqlDialect::MySql)
+        .export(&nul, path.to_str().unwrap())
+        .unwrap();
+    assert!(fs::read_to_string(&path).unwrap().contains("('a\\0b')"));
+}
+
+#[test]
+fn test_sql_create_table_from_pattern_length() {
+    assert_eq!(
+        RegexEngine::new("[A-Z]{2}-[0-9]{3,5}")
+            .unwrap()
+            .max_length(),
+        Some(8)
+    );
+    assert_eq!(
+        RegexEngine::new("(?:abc|d)é?").unwrap().max_length(),
+        Some(4)
+    );
+    assert_eq!(RegexEngine::new("[a-z]+").unwrap().max_length(), None);
+    let generator = DataGenerator::builder("[0-9]{12,13}")
+        .checksum(Checksum::Ean13)
+        .build()
+        .unwrap();
+    assert_eq!(generator.max_length(), Some(13));
+
+    let temp_dir = TempDir::new().unwrap();
+    let schema = Schema::from_toml_str(
+        r#"
+        [[fields]]
+        name = "code"
+        pattern = "[A-Z]{2}[0-9]{4}"
+
+        [[fields]]
+        name = "bio"
+        pattern = "[a-z ]{1,20}[a-z]*"
+    "#,
+    )
+    .unwrap();
+    let mut generator = RecordGenerator::with_seed(&schema, 1).unwrap();
+    let records = generator.generate(3).unwrap();
+
+    let mut exporter = SqlExporter::new(SqlDialect::Sqlite)
+        .with_table("codes")
+        .with_create_table();
+    for column in records.columns() {
+        if let Some(length) = generator.max_length(column) {
+            exporter = exporter.with_max_length(column, length);
+        }
+    }
+    let path = temp_dir.path().join("codes.sql");
+    exporter
+        .export_records(&records, path.to_str().unwrap())
+        .unwrap();
+    let sql = fs::read_to_string(&path).unwrap();
+    assert!(sql.starts_with(concat!(
+        "CREATE TABLE IF NOT EXISTS \"codes\" (\n",
+        "    \"code\" VARCHAR(6),\n",
+        "    \"bio\" TEXT\n",
+        ");\n\n",
+        "INSERT INTO \"codes\" (\"code\", \"bio\") VALUES\n",
+    )));
+    assert_eq!(sql.matches("\n    ('").count(), 3);
+}
+
+#[test]
+#[cfg(feature = "sqlite")]
+fn test_sqlite_export() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("fixtures.db");
+    let path = path.to_str().unwrap();
+
+    let schema = Schema::from_toml_str(
+        r#"
+        [[fields]]
+        name = "code"
+        pattern = "[A-Z]{2}[0-9]{4}"
+
+        [[fields]]
+        name = "note"
+        pattern = "it's [a-z]{3}"
+    "#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 7)
+        .unwrap()
+        .generate(20)
+        .unwrap();
+    let exporter = SqliteExporter::new().with_table("codes");
+    exporter.export_records(&records, path).unwrap();
+    // A second export appends to the existing table
+    exporter.export_records(&records, path).unwrap();
+
+    let connection = rusqlite::Connection::open(path).unwrap();
+    let count: usize = connection
+        .query_row("SELECT COUNT(*) FROM codes", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, 40);
+    let (code, note): (String, String) = connection
+        .query_row("SELECT code, note FROM codes LIMIT 1", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(vec![code, note], records.rows()[0]);
+
+    // A duplicate under a UNIQUE index rolls back the whole export
+    let exporter = SqliteExporter::new()
+        .with_table("ids")
+        .with_column("id")
+        .with_unique_index("id");
+    exporter
+        .export(&["a".to_string(), "b".to_string()], path)
+        .unwrap();
+    assert!(
+        exporter
+            .export(&["c".to_string(), "a".to_string()], path)
+            .is_err()
+    );
+    let ids: Vec<String> = connection
+        .prepare("SELECT id FROM ids ORDER BY id")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(ids, vec!["a", "b"]);
+
+    assert!(
+        SqliteExporter::new()
+            .with_unique_index("missing")
+            .export(&["x".to_string()], path)
+            .is_err()
+    );
+}
+
+#[test]
+#[cfg(feature = "parquet")]
+fn test_parquet_export_row_groups() {
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use parquet::basic::Compression;
+
+    let temp_dir = TempDir::new().unwrap();
+    let schema = Schema::from_toml_str(
+        r#"
+        [[fields]]
+        name = "code"
+        pattern = "[A-Z]{2}[0-9]{4}"
+
+        [[fields]]
+        name = "city"
+        pattern = "(Seoul|Busan|Incheon)"
+    "#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 3)
+        .unwrap()
+        .generate(10)
+        .unwrap();
+    let path = temp_dir.path().join("codes.parquet");
+    ParquetExporter::new()
+        .with_row_group_size(4)
+        .with_compression(ParquetCompression::Zstd)
+        .export_records(&records, path.to_str().unwrap())
+        .unwrap();
+
+    let builder = ParquetRecordBatchReaderBuilder::try_new(fs::File::open(&path).unwrap()).unwrap();
+    let metadata = builder.metadata().clone();
+    let row_groups: Vec<i64> = metadata
+        .row_groups()
+        .iter()
+        .map(|group| group.num_rows())
+        .collect();
+    assert_eq!(row_groups, vec![4, 4, 2]);
+    assert!(matches!(
+        metadata.row_group(0).column(0).compression(),
+        Compression::ZSTD(_)
+    ));
+
+    let mut rows = Vec::new();
+    for batch in builder.build().unwrap() {
+        let batch = batch.unwrap();
+        assert_eq!(batch.schema().field(1).name(), "city");
+        let codes = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<arrow_array::StringArray>()
+            .unwrap();
+        let cities = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<arrow_array::StringArray>()
+            .unwrap();
+        for i in 0..batch.num_rows() {
+            rows.push(vec![
+                codes.value(i).to_string(),
+                cities.value(i).to_string(),
+            ]);
+        }
+    }
+    assert_eq!(rows, records.rows());
+}
+
+#[test]
+#[cfg(feature = "parquet")]
+fn test_parquet_streaming_writer() {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("ids.parquet");
+    let mut generator = DataGenerator::builder("[0-9a-f]{8}")
+        .seed(5)
+        .build()
+        .unwrap();
+
+    let exporter = ParquetExporter::with_headers(vec!["id".to_string()]).with_row_group_size(250);
+    let mut writer = exporter.writer(fs::File::create(&path).unwrap()).unwrap();
+    for _ in 0..4 {
+        for value in generator.generate(200).unwrap() {
+            writer.write(&value).unwrap();
+        }
+    }
+    writer.finish().unwrap();
+
+    let reader = SerializedFileReader::new(fs::File::open(&path).unwrap()).unwrap();
+    assert_eq!(reader.metadata().file_metadata().num_rows(), 800);
+    assert_eq!(reader.metadata().num_row_groups(), 4);
+    assert_eq!(
+        reader
+            .metadata()
+            .file_metadata()
+            .schema_descr()
+            .column(0)
+            .name(),
+        "id"
+    );
+
+    // Plain values fill a single column
+    let two_columns = ParquetExporter::with_headers(vec!["a".to_string(), "b".to_string()]);
+    assert!(
+        two_columns
+            .export(&["x".to_string()], path.to_str().unwrap())
+            .is_err()
+    );
+}
+
+#[test]
+#[cfg(feature = "arrow")]
+fn test_arrow_ipc_export() {
+    use arrow_array::{Array, LargeStringArray, StringArray};
+    use arrow_ipc::reader::{FileReader, StreamReader};
+    use arrow_schema::DataType;
+
+    let temp_dir = TempDir::new().unwrap();
+    let schema = Schema::from_toml_str(
+        r#"
+        [[fields]]
+        name = "code"
+        pattern = "[A-Z]{2}[0-9]{4}"
+
+        [[fields]]
+        name = "word"
+        pattern = "[a-zé]{1,6}"
+    "#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 9)
+        .unwrap()
+        .generate(10)
+        .unwrap();
+
+    // File format, memory-mappable, in batches of 4 rows
+    let path = temp_dir.path().join("codes.arrow");
+    ArrowIpcExporter::new()
+        .with_batch_size(4)
+        .export_records(&records, path.to_str().unwrap())
+        .unwrap();
+    let reader = FileReader::try_new(fs::File::open(&path).unwrap(), None).unwrap();
+    assert_eq!(reader.num_batches(), 3);
+    assert_eq!(reader.schema().field(1).name(), "word");
+    assert_eq!(reader.schema().field(1).data_type(), &DataType::Utf8);
+    let mut rows = Vec::new();
+    for batch in reader {
+        let batch = batch.unwrap();
+        let codes = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let words = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        for i in 0..batch.num_rows() {
+            rows.push(vec![codes.value(i).to_string(), words.value(i).to_string()]);
+        }
+    }
+    assert_eq!(rows, records.rows());
+
+    // Stream format with 64-bit offsets, written as values are generated
+    let path = temp_dir.path().join("ids.arrows");
+    let mut generator = DataGenerator::builder("[0-9a-f]{8}")
+        .seed(2)
+        .build()
+        .unwrap();
+    let exporter = ArrowIpcExporter::with_headers(vec!["id".to_string()])
+        .with_format(ArrowIpcFormat::Stream)
+        .with_batch_size(100)
+        .with_large_utf8();
+    let mut writer = exporter.writer(fs::File::create(&path).unwrap()).unwrap();
+    let values = generator.generate(250).unwrap();
+    for value in &values {
+        writer.write(value).unwrap();
+    }
+    writer.finish().unwrap();
+
+    let reader = StreamReader::try_new(fs::File::open(&path).unwrap(), None).unwrap();
+    assert_eq!(reader.schema().field(0).data_type(), &DataType::LargeUtf8);
+    let batches: Vec<_> = reader.map(Result::unwrap).collect();
+    assert_eq!(
+        batches
+            .iter()
+            .map(|batch| batch.num_rows())
+            .collect::<Vec<_>>(),
+        vec![100, 100, 50]
+    );
+    let first = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<LargeStringArray>()
+        .unwrap();
+    assert_eq!(first.len(), 100);
+    assert_eq!(first.value(0), values[0]);
+}
+
+/// Decode the string records of an Avro container file
+#[cfg(feature = "avro")]
+fn read_avro_strings(
+    path: &std::path::Path,
+) -> (avro_schema::file::FileMetadata, Vec<Vec<String>>) {
+    use avro_schema::read::fallible_streaming_iterator::FallibleStreamingIterator;
+
+    fn read_long(data: &mut &[u8]) -> i64 {
+        let (mut value, mut shift) = (0u64, 0);
+        loop {
+            let byte = data[0];
+            *data = &data[1..];
+            value |= u64::from(byte & 0x7F) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                return (value >> 1) as i64 ^ -((value & 1) as i64);
+            }
+        }
+    }
+
+    let mut file = std::io::BufReader::new(fs::File::open(path).unwrap());
+    let metadata = avro_schema::read::read_metadata(&mut file).unwrap();
+    let mut blocks =
+        avro_schema::read::BlockStreamingIterator::new(file, metadata.compression, metadata.marker);
+    let mut rows = Vec::new();
+    while let Some(block) = blocks.next().unwrap() {
+        let mut data = block.data.as_slice();
+        for _ in 0..block.number_of_rows {
+            let row = metadata
+                .record
+                .fields
+                .iter()
+                .map(|_| {
+                    let length = read_long(&mut data) as usize;
+                    let (value, rest) = data.split_at(length);
+                    data = rest;
+                    String::from_utf8(value.to_vec()).unwrap()
+                })
+                .collect();
+            rows.push(row);
+        }
+        assert!(data.is_empty());
+    }
+    (metadata, rows)
+}
+
+#[test]
+#[cfg(feature = "avro")]
+fn test_avro_export() {
+    use avro_schema::file::Compression;
+
+    let temp_dir = TempDir::new().unwrap();
+    let schema = Schema::from_toml_str(
+        r#"
+        [[fields]]
+        name = "code"
+        pattern = "[A-Z]{2}[0-9]{4}"
+
+        [[fields]]
+        name = "word"
+        pattern = "[a-zé]{1,6}"
+    "#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 4)
+        .unwrap()
+        .generate(5_000)
+        .unwrap();
+
+    for (compression, codec) in [
+        (AvroCompression::None, None),
+        (AvroCompression::Deflate, Some(Compression::Deflate)),
+        (AvroCompression::Snappy, Some(Compression::Snappy)),
+    ] {
+        let path = temp_dir
+            .path()
+            .join(format!("codes-{}.avro", compression.name()));
+        let exporter = AvroExporter::new()
+            .with_record_name("Code")
+            .with_compression(compression);
+        exporter
+            .export_records(&records, path.to_str().unwrap())
+            .unwrap();
+
+        let (metadata, rows) = read_avro_strings(&path);
+        assert_eq!(metadata.compression, codec);
+        assert_eq!(metadata.record.name, "Code");
+        let names: Vec<&str> = metadata
+            .record
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["code", "word"]);
+        assert_eq!(rows, records.rows());
+    }
+
+    let exporter = AvroExporter::with_headers(vec!["email".to_string()]);
+    assert_eq!(
+        exporter.schema(&["email".to_string()]).unwrap(),
+        r#"{"type":"record","name":"GeneratedData","fields":[{"name":"email","type":"string"}]}"#
+    );
+    let path = temp_dir.path().join("emails.avro");
+    exporter
+        .export(&["a@example.com".to_string()], path.to_str().unwrap())
+        .unwrap();
+    assert_eq!(
+        read_avro_strings(&path).1,
+        vec![vec!["a@example.com".to_string()]]
+    );
+
+    // Field names must be valid Avro names
+    let invalid = AvroExporter::with_headers(vec!["e-mail".to_string()]);
+    assert!(
+        invalid
+            .export(&["x".to_string()], path.to_str().unwrap())
+            .is_err()
+    );
+}
+
+#[test]
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+fn test_binary_exports_match_json_layout() {
+    let temp_dir = TempDir::new().unwrap();
+    let data = vec!["alpha".to_string(), "βeta".to_string()];
+    let path = |name: &str| temp_dir.path().join(name).to_str().unwrap().to_string();
+
+    for array_format in [true, false] {
+        JsonExporter::with_options(false, array_format)
+            .export(&data, &path("data.json"))
+            .unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path("data.json")).unwrap()).unwrap();
+
+        #[cfg(feature = "msgpack")]
+        {
+            MessagePackExporter::with_options(array_format)
+                .export(&data, &path("data.msgpack"))
+                .unwrap();
+            let msgpack: serde_json::Value =
+                rmp_serde::from_slice(&fs::read(path("data.msgpack")).unwrap()).unwrap();
+            assert_eq!(msgpack, json);
+        }
+
+        #[cfg(feature = "cbor")]
+        {
+            CborExporter::with_options(array_format)
+                .export(&data, &path("data.cbor"))
+                .unwrap();
+            let cbor: serde_json::Value =
+                ciborium::from_reader(fs::File::open(path("data.cbor")).unwrap()).unwrap();
+            assert_eq!(cbor, json);
+        }
+    }
+
+    let schema = Schema::from_toml_str(
+        r#"
+        [[fields]]
+        name = "name"
+        pattern = "[A-Z][a-z]{3}"
+
+        [[fields]]
+        name = "code"
+        pattern = "[0-9]{4}"
+    "#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 6)
+        .unwrap()
+        .generate(4)
+        .unwrap();
+    JsonExporter::new()
+        .export_records(&records, &path("records.json"))
+        .unwrap();
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(path("records.json")).unwrap()).unwrap();
+    #[cfg(feature = "msgpack")]
+    {
+        MessagePackExporter::new()
+            .export_records(&records, &path("records.msgpack"))
+            .unwrap();
+        let msgpack: serde_json::Value =
+            rmp_serde::from_slice(&fs::read(path("records.msgpack")).unwrap()).unwrap();
+        assert_eq!(msgpack, json);
+    }
+    #[cfg(feature = "cbor")]
+    {
+        CborExporter::new()
+            .export_records(&records, &path("records.cbor"))
+            .unwrap();
+        let cbor: serde_json::Value =
+            ciborium::from_reader(fs::File::open(path("records.cbor")).unwrap()).unwrap();
+        assert_eq!(cbor, json);
+    }
+}
+
+#[test]
+#[cfg(feature = "bson")]
+fn test_bson_export_documents() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("data.bson");
+    let data = vec!["alpha".to_string(), "beta".to_string()];
+
+    let read_documents = || {
+        let bytes = fs::read(&path).unwrap();
+        let mut reader = bytes.as_slice();
+        let mut documents = Vec::new();
+        while !reader.is_empty() {
+            documents.push(bson::Document::from_reader(&mut reader).unwrap());
+        }
+        documents
+    };
+
+    BsonExporter::new()
+        .export(&data, path.to_str().unwrap())
+        .unwrap();
+    assert_eq!(
+        read_documents(),
+        vec![
+            bson::doc! { "value": "alpha" },
+            bson::doc! { "value": "beta" }
+        ]
+    );
+
+    BsonExporter::with_options(false)
+        .export(&data, path.to_str().unwrap())
+        .unwrap();
+    let documents = read_documents();
+    assert_eq!(documents[1].get_str("value").unwrap(), "beta");
+    assert_eq!(documents[1].get("id").and_then(bson::Bson::as_i64), Some(1));
+
+    let schema = Schema::from_toml_str(
+        r#"
+        [[fields]]
+        name = "name"
+        pattern = "[A-Z][a-z]{3}"
+
+        [[fields]]
+        name = "code"
+        pattern = "[0-9]{4}"
+    "#,
+    )
+    .unwrap();
+    let records = RecordGenerator::with_seed(&schema, 6)
+        .unwrap()
+        .generate(3)
+        .unwrap();
+    BsonExporter::new()
+        .export_records(&records, path.to_str().unwrap())
+        .unwrap();
+    let documents = read_documents();
+    assert_eq!(documents.len(), 3);
+    let keys: Vec<&String> = documents[0].keys().collect();
+    assert_eq!(keys, vec!["name", "code"]);
+    assert_eq!(documents[2].get_str("code").unwrap(), records.rows()[2][1]);
+}
+
+#[test]
+fn test_yaml_export_quoting() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("values.yaml");
+    let data: Vec<String> = [
+        "plain text",
+        "yes",
+        "No",
+        "off",
+        "null",
+        "~",
+        "0123",
+        "1e3",
+        "2024-01-01",
+        "12:30",
+        "it's",
+        "key: value",
+        "#tag",
+        "",
+        "line one\nline two",
+        "ends with newline\n",
+        "tab\there",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    YamlExporter::new()
+        .export(&data, path.to_str().unwrap())
+        .unwrap();
+    let yaml = fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        yaml,
+        concat!(
+            "- plain text\n",
+            "- 'yes'\n",
+            "- 'No'\n",
+            "- 'off'\n",
+            "- 'null'\n",
+            "- '~'\n",
+            "- '0123'\n",
+            "- '1e3'\n",
+            "- '2024-01-01'\n",
+            "- '12:30'\n",
+            "- it's\n",
+            "- 'key: value'\n",
+            "- '#tag'\n",
+            "- ''\n",
+            "- |-\n",
+            "  line one\n",
+            "  line two\n",
+            "- |\n",
+            "  ends with newline\n",
+            "- \"tab\\there\"\n",
+        )
+    );
+    let parsed: Vec<String> = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(parsed, data);
+}
+
+#[test]
+fn test_yaml_and_toml_layouts() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = |name: &str| temp_dir.path().join(name).to_str().unwrap().to_string();
+    let data = vec!["true".to_string(), "007".to_string(), "a\nb".to_string()];
+
+    YamlExporter::with_layout(DocumentLayout::Objects)
+        .export(&data, &path("objects.yaml"))
+        .unwrap();
+    let objects: serde_json::Value =
+        serde_yaml::from_str(&fs::read_to_string(path("objects.yaml")).unwrap()).unwrap();
+    assert_eq!(objects[1], serde_json::json!({ "id": 1, "value": "007" }));
+
+    YamlExporter::with_layout(DocumentLayout::Map)
+        .export(&data, &path("map.yaml"))
+        .unwrap();
+    let map: serde_json::Value =
+        serde_yaml::from_str(&fs::read_to_string(path("map.yaml")).unwrap()).unwrap();
+    assert_eq!(map, serde_json::json!({ "true": 0, "007": 1, "a\nb": 2 }));
+
+    TomlExporter::new()
+        .export(&data, &path("list.toml"))
+        .unwrap();
+    let list: toml::Table = fs::read_to_string(path("list.toml"))
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(
+        list["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect::<Vec<_>>(),
+        data
+    );
+
+    TomlExporter::with_layout(DocumentLayout::Map)
+        .export(&data, &path("map.toml"))
+        .unwrap();
+    let map: toml::Table = fs::read_to_string(path("map.toml"))
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(map["007"].as_integer(), Some(1));
+
+    // Records keyed by their first column
+    let mut records =
+        regex_data_gen_core::RecordSet::new(vec!["id".to_string(), "city".to_string()]);
+    records
+        .push_row(vec!["no".to_string(), "Seoul".to_string()])
+        .unwrap();
+    records
+        .push_row(vec!["on".to_string(), "Busan".to_string()])
+        .unwrap();
+    records
+        .push_row(vec!["001".to_string(), "Seoul".to_string()])
+        .unwrap();
+    TomlExporter::with_layout(DocumentLayout::Map)
+        .export_records(&records, &path("records.toml"))
+        .unwrap();
+    let table: toml::Table = fs::read_to_string(path("records.toml"))
+        .unwrap()
+        .parse()
+        .unwrap();
+    YamlExporter::with_layout(DocumentLayout::Map)
+        .export_records(&records, &path("records.yaml"))
+        .unwrap();
+    let yaml: serde_json::Value =
+        serde_yaml::from_str(&fs::read_to_string(path("records.yaml")).unwrap()).unwrap();
+    for row in records.rows() {
+        assert_eq!(table[&row[0]]["city"].as_str(), Some(row[1].as_str()));
+        assert_eq!(yaml[&row[0]]["city"], row[1]);
+    }
+
+    let duplicates = vec!["same".to_string(), "same".to_string()];
+    assert!(
+        YamlExporter::with_layout(DocumentLayout::Map)
+            .export(&duplicates, &path("dup.yaml"))
+            .is_err()
+    );
+
+    // The map layout keys records by their first column
+    let mut no_columns = regex_data_gen_core::RecordSet::new(Vec::new());
+    no_columns.push_row(Vec::new()).unwrap();
+    let error = YamlExporter::with_layout(DocumentLayout::Map)
+        .export_records(&no_columns, &path("empty.yaml"))
+        .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Map layout needs at least one column")
+    );
+    assert!(
+        TomlExporter::with_layout(DocumentLayout::Map)
+            .export_records(&no_columns, &path("empty.toml"))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_fixed_width_export() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("accounts.dat");
+    let path = path.to_str().unwrap();
+
+    let mut records =
+        regex_data_gen_core::RecordSet::new(vec!["id".to_string(), "name".to_string()]);
+    records
+        .push_row(vec!["42".to_string(), "ALICE".to_string()])
+        .unwrap();
+    records
+        .push_row(vec!["7".to_string(), "BO".to_string()])
+        .unwrap();
+
+    // Declared widths, otherwise the longest value
+    FixedWidthExporter::new()
+        .with_width("id", 4)
+        .export_records(&records, path)
+        .unwrap();
+    assert_eq!(fs::read_to_string(path).unwrap(), "42  ALICE\n7   BO   \n");
+
+    let mut generator = DataGenerator::new("[0-9]{1,6}").unwrap();
+    let width = generator.max_length().unwrap();
+    let data = generator.generate(50).unwrap();
+    FixedWidthExporter::new()
+        .with_width("generated_data", width)
+        .with_alignment(Alignment::Right)
+        .with_pad_char('0')
+        .export(&data, path)
+        .unwrap();
+    let content = fs::read_to_string(path).unwrap();
+    for (line, value) in content.lines().zip(&data) {
+        assert_eq!(line.len(), 6);
+        assert_eq!(line.trim_start_matches('0'), value.trim_start_matches('0'));
+    }
+    assert_eq!(content.lines().count(), 50);
+}
+
+#[test]
+fn test_fixed_width_overflow() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("codes.dat");
+    let path = path.to_str().unwrap();
+    let data = vec!["ABCDEF".to_string(), "ÄÖ".to_string()];
+
+    let error = FixedWidthExporter::new()
+        .with_width("generated_data", 4)
+        .export(&data, path)
+        .unwrap_err();
+    assert!(error.to_string().contains("Record 1"));
+
+    FixedWidthExporter::new()
+        .with_default_width(4)
+        .with_truncation()
+        .export(&data, path)
+        .unwrap();
+    assert_eq!(fs::read_to_string(path).unwrap(), "ABCD\nÄÖ  \n");
+
+    FixedWidthExporter::new()
+        .with_default_width(4)
+        .with_alignment(Alignment::Right)
+        .with_truncation()
+        .export(&data, path)
+        .unwrap();
+    assert_eq!(fs::read_to_string(path).unwrap(), "CDEF\n  ÄÖ\n");
+
+    let multiline = vec!["a\nb".to_string()];
+    assert!(FixedWidthExporter::new().export(&multiline, path).is_err());
+}
+
+#[test]
+fn test_markdown_export_escapes_cells() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sample.md");
+    let path = path.to_str().unwrap();
+
+    let data = vec![
+        "a|b".to_string(),
+        "`code`".to_string(),
+        "x\\|y".to_string(),
+        "two\nlines".to_string(),
+    ];
+    MarkdownExporter::with_headers(vec!["Value".to_string()])
+        .export(&data, path)
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(path).unwrap(),
+        "| Value |\n| --- |\n| a\\|b |\n| \\`code\\` |\n| x\\\\\\|y |\n| two<br>lines |\n"
+    );
+
+    let mut records =
+        regex_data_gen_core::RecordSet::new(vec!["id".to_string(), "name".to_string()]);
+    records
+        .push_row(vec!["1".to_string(), "*bold*".to_string()])
+        .unwrap();
+    MarkdownExporter::new()
+        .export_records(&records, path)
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(path).unwrap(),
+        "| id | name |\n| --- | --- |\n| 1 | \\*bold\\* |\n"
+    );
+
+    let two_headers = MarkdownExporter::with_headers(vec!["a".to_string(), "b".to_string()]);
+    assert!(two_headers.export(&data, path).is_err());
+}
+
+#[test]
+fn test_html_export_escapes_entities() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sample.html");
+    let path = path.to_str().unwrap();
+
+    let mut records =
+        regex_data_gen_core::RecordSet::new(vec!["<id>".to_string(), "quote".to_string()]);
+    records
+        .push_row(vec!["1".to_string(), "Tom & \"Jerry's\" <b>".to_string()])
+        .unwrap();
+    HtmlExporter::new()
+        .with_title("Q&A")
+        .export_records(&records, path)
+        .unwrap();
+
+    let html = fs::read_to_string(path).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>\n"));
+    assert!(html.contains("<title>Q&amp;A</title>"));
+    assert!(html.contains("<tr><th>&lt;id&gt;</th><th>quote</th></tr>"));
+    assert!(
+        html.contains("<tr><td>1</td><td>Tom &amp; &quot;Jerry&#39;s&quot; &lt;b&gt;</td></tr>")
+    );
+    assert!(html.trim_end().ends_with("</html>"));
+
+    HtmlExporter::with_headers(vec!["email".to_string()])
+        .export(&["a@example.com".to_string()], path)
+        .unwrap();
+    let html = fs::read_to_string(path).unwrap();
+    assert!(html.contains(
+        "<tr><th>email</th></tr>\n</thead>\n<tbody>\n<tr><td>a@example.com</td></tr>\n</tbody>"
+    ));
+}
+
+#[test]
+fn test_export_records_default_for_value_exporters() {
+    // An exporter written before records existed
+    struct LinesExporter;
+
+    impl Exporter for LinesExporter {
+        fn export(&self, data: &[String], output_path: &str) -> regex_data_gen_core::Result<()> {
+            fs::write(output_path, data.join("\n")).unwrap();
+            Ok(())
+        }
+
+        fn format_name(&self) -> &'static str {
+            "lines"
+        }
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("values.txt");
+    let path = path.to_str().unwrap();
+
+    let mut records = regex_data_gen_core::RecordSet::new(vec!["id".to_string()]);
+    records.push_row(vec!["1".to_string()]).unwrap();
+    records.push_row(vec!["2".to_string()]).unwrap();
+    LinesExporter.export_records(&records, path).unwrap();
+    assert_eq!(fs::read_to_string(path).unwrap(), "1\n2");
+
+    let mut records =
+        regex_data_gen_core::RecordSet::new(vec!["id".to_string(), "name".to_string()]);
+    records
+        .push_row(vec!["1".to_string(), "a".to_string()])
+        .unwrap();
+    let error = LinesExporter.export_records(&records, path).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("lines export does not support records with 2 columns")
+    );
+}
+
+#[test]
+fn test_xml_export_records_sanitizes_element_names() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("records.xml");
+    let path = path.to_str().unwrap();
+
+    let columns = ["id", "first name", "1id", "a:b", "ok-name.x"];
+    let mut records =
+        regex_data_gen_core::RecordSet::new(columns.iter().map(|c| c.to_string()).collect());
+    records
+        .push_row(vec![
+            "1".into(),
+            "Ann".into(),
+            "7".into(),
+            "x".into(),
+            "y".into(),
+        ])
+        .unwrap();
+    XmlExporter::new().export_records(&records, path).unwrap();
+
+    let xml = fs::read_to_string(path).unwrap();
+    assert!(xml.contains("<id>1</id>"));
+    assert!(xml.contains(r#"<first_name name="first name">Ann</first_name>"#));
+    assert!(xml.contains(r#"<_1id name="1id">7</_1id>"#));
+    assert!(xml.contains(r#"<a_b name="a:b">x</a_b>"#));
+    assert!(xml.contains("<ok-name.x>y</ok-name.x>"));
+
+    // Every element is well formed
+    let mut reader = quick_xml::Reader::from_str(&xml);
+    loop {
+        match reader.read_event().unwrap() {
+            quick_xml::events::Event::Eof => break,
+            _ => continue,
+        }
+    }
+}